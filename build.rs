@@ -2,6 +2,7 @@ use std::{env, fs, io, path::PathBuf};
 
 fn main() -> io::Result<()> {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
 
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set by Cargo");
 
@@ -19,14 +20,58 @@ fn main() -> io::Result<()> {
     let is_release = profile == "release";
     let version = env::var("CARGO_PKG_VERSION").unwrap_or_else(|_| "unknown".to_string());
 
+    // Protokoll-Hash: nur Clients mit derselben Protokollversion und denselben
+    // Netzwerk-Abhängigkeiten können sich verbinden. Cargo.lock ist nicht
+    // eingecheckt, daher zählen die Angaben aus Cargo.toml.
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set by Cargo");
+    let manifest = fs::read_to_string(PathBuf::from(manifest_dir).join("Cargo.toml"))
+        .unwrap_or_else(|err| {
+            println!(
+                "cargo:warning=Cargo.toml unreadable ({err}) → protocol hash from version only"
+            );
+            String::new()
+        });
+    let protocol_hash = protocol_hash(&manifest);
+
     let config = format!(
         r#"pub const STEAM_APP_ID: u32 = {};
 pub const IS_RELEASE: bool = {};
 pub const BUILD_PROFILE: &str = "{}";
-pub const VERSION: &str = "{}";"#,
-        appid_str, is_release, profile, version
+pub const VERSION: &str = "{}";
+pub const PROTOCOL_HASH: &str = "{:016x}";"#,
+        appid_str, is_release, profile, version, protocol_hash
     );
     fs::write(PathBuf::from(&out_dir).join("config.rs"), config)?;
 
     Ok(())
 }
+
+/// Bei jeder inkompatiblen Änderung am eigenen Netzwerkprotokoll erhöhen
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Abhängigkeiten, deren Version das Netzwerkprotokoll bestimmt
+const PROTOCOL_DEPENDENCIES: [&str; 3] = ["chicken", "bevy_replicon", "aeronet_replicon"];
+
+/// FNV-1a über [`PROTOCOL_VERSION`] und die in Cargo.toml angegebenen Versionen
+/// (bei Git inkl. Tag) der Netzwerk-Abhängigkeiten. Fehlende Einträge zählen leer.
+fn protocol_hash(manifest: &str) -> u64 {
+    let dependencies = PROTOCOL_DEPENDENCIES.iter().map(|name| {
+        let spec = manifest
+            .lines()
+            .find_map(|line| {
+                line.trim()
+                    .strip_prefix(name)?
+                    .trim_start()
+                    .strip_prefix('=')
+            })
+            .unwrap_or_default();
+        format!("{name} {}", spec.trim())
+    });
+    [format!("protocol {PROTOCOL_VERSION}")]
+        .into_iter()
+        .chain(dependencies)
+        .flat_map(|line| line.into_bytes().into_iter().chain([b'\n']))
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
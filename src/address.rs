@@ -0,0 +1,279 @@
+use {
+    bevy::{
+        prelude::*,
+        tasks::{IoTaskPool, Task, futures::check_ready},
+    },
+    bevy_egui::egui,
+    chicken::network::client::SetClientTarget,
+    std::{
+        fmt,
+        net::{IpAddr, Ipv6Addr, SocketAddr, ToSocketAddrs},
+    },
+};
+
+/// Plugin für die Adresseingabe in "Join Game".
+///
+/// Hostnamen werden im Hintergrund aufgelöst; erst die aufgelöste Adresse wird als
+/// `ClientTarget` an chicken übergeben, das nur IP-Literale versteht.
+pub struct AddressPlugin;
+
+impl Plugin for AddressPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JoinAddress>()
+            .add_systems(Update, poll_join_address);
+    }
+}
+
+/// Grund, warum eine Serveradresse nicht verwendet werden kann
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    Empty,
+    InvalidHost,
+    InvalidPort,
+    UnclosedBracket,
+    /// Auflösung fehlgeschlagen, mit Meldung des Resolvers
+    Resolve(String),
+    NotFound,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::Empty => write!(f, "Enter a server address"),
+            AddressError::InvalidHost => write!(f, "Invalid host name"),
+            AddressError::InvalidPort => write!(f, "Port must be a number from 1 to 65535"),
+            AddressError::UnclosedBracket => write!(f, "Missing ']' after IPv6 address"),
+            AddressError::Resolve(err) => write!(f, "Could not resolve host: {err}"),
+            AddressError::NotFound => write!(f, "Host has no address"),
+        }
+    }
+}
+
+/// Eingegebene Serveradresse: `host`, `host:port`, `[ipv6]:port` oder ein IP-Literal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ServerAddress {
+    /// Zerlegt die Eingabe; ohne Port gilt `default_port`
+    pub fn parse(input: &str, default_port: u16) -> Result<Self, AddressError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(AddressError::Empty);
+        }
+
+        let (host, port) = if let Some(rest) = input.strip_prefix('[') {
+            let (host, rest) = rest.split_once(']').ok_or(AddressError::UnclosedBracket)?;
+            if host.parse::<Ipv6Addr>().is_err() {
+                return Err(AddressError::InvalidHost);
+            }
+            let port = match rest {
+                "" => None,
+                rest => Some(rest.strip_prefix(':').ok_or(AddressError::InvalidPort)?),
+            };
+            (host, port)
+        } else if input.matches(':').count() > 1 {
+            // IPv6 ohne Klammern kann keinen Port enthalten
+            (input, None)
+        } else {
+            match input.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (input, None),
+            }
+        };
+
+        let port = match port {
+            None => default_port,
+            Some(port) => port
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port != 0)
+                .ok_or(AddressError::InvalidPort)?,
+        };
+        if host.is_empty() || host.contains(char::is_whitespace) || host.contains('/') {
+            return Err(AddressError::InvalidHost);
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+        })
+    }
+
+    /// Die Adresse, falls der Host bereits eine IP ist
+    pub fn literal(&self) -> Option<SocketAddr> {
+        let ip = self.host.parse::<IpAddr>().ok()?;
+        Some(SocketAddr::new(ip, self.port))
+    }
+
+    /// Löst den Host blockierend auf; IPv4-Adressen werden bevorzugt
+    pub fn resolve(&self) -> Result<SocketAddr, AddressError> {
+        if let Some(address) = self.literal() {
+            return Ok(address);
+        }
+        let addresses: Vec<SocketAddr> = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|err| AddressError::Resolve(err.to_string()))?
+            .collect();
+        addresses
+            .iter()
+            .find(|address| address.is_ipv4())
+            .or(addresses.first())
+            .copied()
+            .ok_or(AddressError::NotFound)
+    }
+
+    /// Löst den Host im IO-Taskpool auf, ohne den Frame zu blockieren
+    pub fn resolve_async(self) -> Task<Result<SocketAddr, AddressError>> {
+        IoTaskPool::get().spawn(async move { self.resolve() })
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Stand der Auflösung einer Adresseingabe
+#[derive(Debug, Default)]
+pub enum ResolveState {
+    #[default]
+    Empty,
+    Resolving(Task<Result<SocketAddr, AddressError>>),
+    Resolved(SocketAddr),
+    Failed(AddressError),
+}
+
+/// Adressfeld in "Join Game"
+#[derive(Resource, Debug, Default)]
+pub struct JoinAddress {
+    /// Eingabe des Spielers, z.B. `fos.lan:8080`
+    pub input: String,
+    pub state: ResolveState,
+    /// Zuletzt an chicken übergebenes Ziel
+    applied: Option<String>,
+}
+
+impl JoinAddress {
+    /// Übernimmt eine neue Eingabe und startet ggf. die Auflösung
+    pub fn set_input(&mut self, input: String, default_port: u16) {
+        self.state = match ServerAddress::parse(&input, default_port) {
+            Err(AddressError::Empty) => ResolveState::Empty,
+            Err(err) => ResolveState::Failed(err),
+            Ok(address) => match address.literal() {
+                Some(resolved) => ResolveState::Resolved(resolved),
+                None => ResolveState::Resolving(address.resolve_async()),
+            },
+        };
+        self.input = input;
+    }
+
+    pub fn resolved(&self) -> Option<SocketAddr> {
+        match self.state {
+            ResolveState::Resolved(address) => Some(address),
+            _ => None,
+        }
+    }
+}
+
+/// Übernimmt fertige Auflösungen und hält `ClientTarget` auf der aufgelösten Adresse
+fn poll_join_address(mut commands: Commands, mut join: ResMut<JoinAddress>) {
+    if let ResolveState::Resolving(task) = &mut join.state
+        && let Some(result) = check_ready(task)
+    {
+        join.state = match result {
+            Ok(address) => ResolveState::Resolved(address),
+            Err(err) => ResolveState::Failed(err),
+        };
+    }
+
+    let target = join
+        .resolved()
+        .map(|address| address.to_string())
+        .unwrap_or_default();
+    if join.applied.as_ref() != Some(&target) {
+        commands.queue(SetClientTarget {
+            input: target.clone(),
+        });
+        join.applied = Some(target);
+    }
+}
+
+// --- UI ---
+
+/// Rendert das Adressfeld mit Auflösungsstatus
+pub fn render_address_input(ui: &mut egui::Ui, join: &mut JoinAddress, default_port: u16) {
+    ui.horizontal(|ui| {
+        let mut input = join.input.clone();
+        let hint = format!("fos.lan:{default_port}");
+        if ui
+            .add(egui::TextEdit::singleline(&mut input).hint_text(hint))
+            .changed()
+        {
+            join.set_input(input, default_port);
+        }
+
+        match &join.state {
+            ResolveState::Empty => {}
+            ResolveState::Resolving(_) => {
+                ui.add(egui::Spinner::new());
+                ui.label("Resolving…");
+            }
+            ResolveState::Resolved(address) => {
+                if join.input.trim() == address.to_string() {
+                    ui.label("✔");
+                } else {
+                    ui.label(format!("→ {address}"));
+                }
+            }
+            ResolveState::Failed(err) => {
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), err.to_string());
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<ServerAddress, AddressError> {
+        ServerAddress::parse(input, 8080)
+    }
+
+    #[test]
+    fn addresses_are_parsed_with_default_port() {
+        let address = |host: &str, port| {
+            Ok(ServerAddress {
+                host: host.to_string(),
+                port,
+            })
+        };
+        assert_eq!(parse(" fos.lan:9000 "), address("fos.lan", 9000));
+        assert_eq!(parse("fos.lan"), address("fos.lan", 8080));
+        assert_eq!(parse("[::1]:9000"), address("::1", 9000));
+        assert_eq!(parse("[::1]"), address("::1", 8080));
+        assert_eq!(parse("fe80::1"), address("fe80::1", 8080));
+
+        assert_eq!(parse(""), Err(AddressError::Empty));
+        assert_eq!(parse("fos.lan:0"), Err(AddressError::InvalidPort));
+        assert_eq!(parse("fos.lan:http"), Err(AddressError::InvalidPort));
+        assert_eq!(parse("[::1:9000"), Err(AddressError::UnclosedBracket));
+        assert_eq!(parse("[fos.lan]:9000"), Err(AddressError::InvalidHost));
+        assert_eq!(parse("fos lan"), Err(AddressError::InvalidHost));
+    }
+
+    #[test]
+    fn literals_resolve_without_lookup() {
+        let address = parse("[::1]:9000").unwrap();
+        assert_eq!(address.to_string(), "[::1]:9000");
+        assert_eq!(address.literal(), Some("[::1]:9000".parse().unwrap()));
+        assert_eq!(parse("fos.lan").unwrap().literal(), None);
+    }
+}
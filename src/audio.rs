@@ -0,0 +1,202 @@
+use {
+    crate::settings::{AudioSettings, UserSettings},
+    bevy::{audio::Volume, ecs::system::SystemParam, prelude::*, window::PrimaryWindow},
+    bevy_egui::{EguiContext, EguiOutput, EguiPostUpdateSet, PrimaryEguiContext, egui},
+    chicken::{identity::PlayerIdentity, protocols::ServerChat},
+};
+
+const UI_CLICK_SOUND: &str = "audio/ui_click.wav";
+const UI_HOVER_SOUND: &str = "audio/ui_hover.wav";
+const CHAT_NOTIFICATION_SOUND: &str = "audio/chat_notification.wav";
+
+/// Plugin für Lautstärke-Busse, UI-Sounds und Chat-Benachrichtigungen.
+///
+/// Jeder abgespielte Sound bekommt einen [`AudioBus`]; seine Lautstärke ergibt
+/// sich aus Master × Bus aus den `UserSettings` und wird live nachgeführt.
+pub struct AudioMixerPlugin;
+
+impl Plugin for AudioMixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_sounds)
+            .add_systems(Update, (update_bus_volumes, play_chat_notification))
+            .add_systems(
+                PostUpdate,
+                play_ui_sounds.after(EguiPostUpdateSet::ProcessOutput),
+            );
+    }
+}
+
+/// Lautstärke-Bus eines Sounds
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioBus {
+    Music,
+    Effects,
+    Ui,
+    Chat,
+}
+
+impl AudioBus {
+    /// Effektive lineare Lautstärke (Master × Bus)
+    pub fn volume(self, audio: &AudioSettings) -> f32 {
+        let bus = match self {
+            AudioBus::Music => audio.music_volume,
+            AudioBus::Effects => audio.effects_volume,
+            AudioBus::Ui => audio.ui_volume,
+            AudioBus::Chat => audio.chat_volume,
+        };
+        audio.master_volume * bus
+    }
+}
+
+#[derive(Resource)]
+struct Sounds {
+    ui_click: Handle<AudioSource>,
+    ui_hover: Handle<AudioSource>,
+    chat_notification: Handle<AudioSource>,
+}
+
+fn load_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Sounds {
+        ui_click: asset_server.load(UI_CLICK_SOUND),
+        ui_hover: asset_server.load(UI_HOVER_SOUND),
+        chat_notification: asset_server.load(CHAT_NOTIFICATION_SOUND),
+    });
+}
+
+/// Zugriff auf die aktuellen Bus-Lautstärken inkl. Stummschaltung ohne Fensterfokus
+#[derive(SystemParam)]
+pub struct AudioMixer<'w, 's> {
+    commands: Commands<'w, 's>,
+    settings: Res<'w, UserSettings>,
+    window: Option<Single<'w, 's, &'static Window, With<PrimaryWindow>>>,
+}
+
+impl AudioMixer<'_, '_> {
+    pub fn volume(&self, bus: AudioBus) -> f32 {
+        let audio = &self.settings.audio;
+        let unfocused = self.window.as_ref().is_some_and(|window| !window.focused);
+        if audio.mute_when_unfocused && unfocused {
+            0.0
+        } else {
+            bus.volume(audio)
+        }
+    }
+
+    /// Spielt einen Sound einmalig auf dem angegebenen Bus ab
+    pub fn play(&mut self, sound: Handle<AudioSource>, bus: AudioBus) {
+        let volume = self.volume(bus);
+        if volume <= 0.0 {
+            return;
+        }
+        self.commands.spawn((
+            AudioPlayer(sound),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+            bus,
+        ));
+    }
+}
+
+/// Führt laufende Sounds den aktuellen Einstellungen und dem Fensterfokus nach
+fn update_bus_volumes(mixer: AudioMixer, mut sinks: Query<(&AudioBus, &mut AudioSink)>) {
+    for (bus, mut sink) in &mut sinks {
+        let volume = Volume::Linear(mixer.volume(*bus));
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+fn play_chat_notification(
+    mut mixer: AudioMixer,
+    mut messages: MessageReader<ServerChat>,
+    sounds: Option<Res<Sounds>>,
+    identity: Option<Res<PlayerIdentity>>,
+) {
+    let Some(sounds) = sounds else {
+        messages.clear();
+        return;
+    };
+    let own_name = identity.as_ref().map(|id| id.display_name.as_str());
+
+    // Eigene Nachrichten lösen keinen Ton aus, mehrere pro Frame nur einen
+    if messages
+        .read()
+        .any(|msg| Some(msg.sender_name.as_str()) != own_name)
+    {
+        mixer.play(sounds.chat_notification.clone(), AudioBus::Chat);
+    }
+}
+
+/// Klick- und Hover-Sounds für alle egui-Widgets des primären Kontexts.
+/// Hover zählt nur bei klickbaren Widgets; Fokuswechsel per Tastatur/Gamepad
+/// klingen wie Hover.
+fn play_ui_sounds(
+    mut mixer: AudioMixer,
+    sounds: Option<Res<Sounds>>,
+    mut contexts: Query<(&mut EguiContext, &EguiOutput), With<PrimaryEguiContext>>,
+    mut last_hovered: Local<Option<egui::Id>>,
+) {
+    let Some(sounds) = sounds else {
+        return;
+    };
+    let Ok((mut context, output)) = contexts.single_mut() else {
+        return;
+    };
+    let ctx = context.get_mut();
+
+    let hovered = ctx
+        .interaction_snapshot(|snapshot| snapshot.hovered.clone())
+        .into_iter()
+        .find(|id| {
+            ctx.read_response(*id).is_some_and(|response| {
+                response.enabled() && response.sense.senses_click() && !response.sense.senses_drag()
+            })
+        });
+    let hover_started = hovered.is_some() && hovered != *last_hovered;
+    *last_hovered = hovered;
+
+    let mut clicked = false;
+    let mut focused = false;
+    for event in &output.platform_output.events {
+        match event {
+            egui::output::OutputEvent::Clicked(_) => clicked = true,
+            egui::output::OutputEvent::FocusGained(_) => focused = true,
+            _ => {}
+        }
+    }
+
+    if clicked {
+        mixer.play(sounds.ui_click.clone(), AudioBus::Ui);
+    } else if hover_started || focused {
+        mixer.play(sounds.ui_hover.clone(), AudioBus::Ui);
+    }
+}
+
+// --- UI ---
+
+/// Rendert die Audio-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_audio_settings(ui: &mut egui::Ui, audio: &mut AudioSettings) -> bool {
+    let mut changed = false;
+    for (volume, label) in [
+        (&mut audio.master_volume, "Master"),
+        (&mut audio.music_volume, "Music"),
+        (&mut audio.effects_volume, "Effects"),
+        (&mut audio.ui_volume, "Interface"),
+        (&mut audio.chat_volume, "Chat notifications"),
+    ] {
+        changed |= ui
+            .add(
+                egui::Slider::new(volume, AudioSettings::VOLUME)
+                    .custom_formatter(|value, _| format!("{:.0}%", value * 100.0))
+                    .text(label),
+            )
+            .changed();
+    }
+    changed |= ui
+        .checkbox(
+            &mut audio.mute_when_unfocused,
+            "Mute when window is unfocused",
+        )
+        .changed();
+    changed
+}
@@ -1,4 +1,8 @@
 use {
+    crate::{
+        input::{ActionState, InputAction},
+        settings::{Language, LanguageOverride, UserSettings},
+    },
     bevy::prelude::*,
     bevy_egui::{EguiContexts, egui},
     chicken::{
@@ -27,6 +31,7 @@ impl Plugin for ChatPlugin {
                     update_autocomplete_data,
                     update_error_timer,
                     handle_chat_input,
+                    flush_chat_outbox.after(handle_chat_input),
                 )
                     .run_if(
                         in_state(ServerStatus::Running)
@@ -75,7 +80,7 @@ pub struct AutocompleteItem {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Resource)]
+#[derive(Debug, Default, Resource)]
 pub struct AutocompleteState {
     /// Verfügbare Commands
     pub commands: Vec<ChatCommandInfo>,
@@ -95,21 +100,6 @@ pub struct AutocompleteState {
     pub trigger_position: usize,
 }
 
-impl Default for AutocompleteState {
-    fn default() -> Self {
-        Self {
-            commands: Vec::new(),
-            players: Vec::new(),
-            visible: false,
-            filtered_items: Vec::new(),
-            selected_index: 0,
-            trigger_char: None,
-            filter_text: String::new(),
-            trigger_position: 0,
-        }
-    }
-}
-
 /// Haupt-Resource für den Chat-Status
 #[derive(Resource)]
 pub struct ChatState {
@@ -117,6 +107,8 @@ pub struct ChatState {
     pub messages: Vec<ChatEntry>,
     /// Aktueller Eingabetext
     pub input: String,
+    /// Abgeschickte, aber noch nicht gesendete Nachrichten (auch aus Headless-Skripten)
+    pub outbox: Vec<String>,
     /// Ist der Chat geöffnet?
     pub is_open: bool,
     /// Hat der Chat Fokus?
@@ -138,6 +130,7 @@ impl Default for ChatState {
         Self {
             messages: Vec::with_capacity(CHAT_CLIENT_HISTORY_SIZE),
             input: String::new(),
+            outbox: Vec::new(),
             is_open: false,
             has_focus: false,
             history_loaded: false,
@@ -167,10 +160,19 @@ fn receive_chat_messages(
         chat_state.scroll_to_bottom = true;
     }
 
-    // History-Response empfangen
+    // History-Response empfangen; nach einem Wiederverbinden sind ältere Einträge
+    // schon vorhanden
     for response in history_events.read() {
         for msg in &response.history {
-            chat_state.messages.push(ChatEntry::from(msg.clone()));
+            let known = msg.timestamp.is_some()
+                && chat_state.messages.iter().any(|entry| {
+                    entry.timestamp == msg.timestamp
+                        && entry.sender_name == msg.sender_name
+                        && entry.text == msg.text
+                });
+            if !known {
+                chat_state.messages.push(ChatEntry::from(msg.clone()));
+            }
         }
         chat_state.history_loaded = true;
         chat_state.scroll_to_bottom = true;
@@ -181,15 +183,18 @@ fn receive_chat_messages(
 fn handle_chat_errors(
     mut chat_state: ResMut<ChatState>,
     mut error_events: MessageReader<ServerChatError>,
+    settings: Res<UserSettings>,
+    language: Option<Res<LanguageOverride>>,
 ) {
+    let language = settings.language(language.as_deref());
     for error in error_events.read() {
-        // Erstelle einen Timer für 5 Sekunden Anzeige
-        let timer = Timer::from_seconds(5.0, TimerMode::Once);
+        // Timer für die eingestellte Anzeigedauer
+        let timer = Timer::from_seconds(settings.chat.error_display_secs, TimerMode::Once);
 
         chat_state.error_message = Some((
             format!(
                 "[{}] {}",
-                format_error_type(&error.error_type),
+                format_error_type(&error.error_type, language),
                 error.message
             ),
             timer,
@@ -198,12 +203,18 @@ fn handle_chat_errors(
 }
 
 /// Formatiert den Error-Typ für die Anzeige
-fn format_error_type(error_type: &chicken::protocols::ChatErrorType) -> &'static str {
+fn format_error_type(
+    error_type: &chicken::protocols::ChatErrorType,
+    language: Language,
+) -> &'static str {
     use chicken::protocols::ChatErrorType;
-    match error_type {
-        ChatErrorType::MessageTooLong => "Zu lang",
-        ChatErrorType::EmptyMessage => "Leere Nachricht",
-        ChatErrorType::UnknownCommand => "Unbekannter Befehl",
+    match (language, error_type) {
+        (Language::German, ChatErrorType::MessageTooLong) => "Zu lang",
+        (Language::German, ChatErrorType::EmptyMessage) => "Leere Nachricht",
+        (Language::German, ChatErrorType::UnknownCommand) => "Unbekannter Befehl",
+        (Language::English, ChatErrorType::MessageTooLong) => "Too long",
+        (Language::English, ChatErrorType::EmptyMessage) => "Empty message",
+        (Language::English, ChatErrorType::UnknownCommand) => "Unknown command",
     }
 }
 
@@ -230,30 +241,23 @@ fn update_autocomplete_data(
 }
 
 /// Verarbeitet Chat-Eingabe (Tasten, Senden, etc.)
-fn handle_chat_input(
-    mut chat_state: ResMut<ChatState>,
-    keys: Res<ButtonInput<KeyCode>>,
-    mut chat_writer: MessageWriter<ClientChat>,
-) {
-    // Chat mit Enter öffnen/schließen (oder T)
+fn handle_chat_input(mut chat_state: ResMut<ChatState>, actions: Res<ActionState>) {
+    // Chat öffnen (Standard: Enter oder T)
     if !chat_state.is_open {
-        if keys.just_pressed(KeyCode::Enter) || keys.just_pressed(KeyCode::KeyT) {
+        if actions.just_pressed(InputAction::OpenChat) {
             chat_state.is_open = true;
             chat_state.has_focus = true;
         }
         return;
     }
 
-    // Senden mit Enter
-    if keys.just_pressed(KeyCode::Enter) && chat_state.has_focus {
+    // Senden (Standard: Enter)
+    if actions.just_pressed(InputAction::SendChat) && chat_state.has_focus {
         if !chat_state.input.trim().is_empty() {
             // Client-seitige Validierung
             if chat_state.input.len() <= CHAT_MESSAGE_MAX_LENGTH {
-                let message = ClientChat {
-                    text: chat_state.input.clone(),
-                };
-                chat_writer.write(message);
-                chat_state.input.clear();
+                let text = std::mem::take(&mut chat_state.input);
+                chat_state.outbox.push(text);
             }
         } else {
             // Leere Eingabe = Chat schließen
@@ -262,8 +266,8 @@ fn handle_chat_input(
         }
     }
 
-    // Chat mit Escape schließen
-    if keys.just_pressed(KeyCode::Escape) && chat_state.is_open {
+    // Chat schließen (Standard: Escape)
+    if actions.just_pressed(InputAction::CloseChat) && chat_state.is_open {
         chat_state.is_open = false;
         chat_state.has_focus = false;
         chat_state.autocomplete.visible = false;
@@ -272,23 +276,22 @@ fn handle_chat_input(
 
     // Autocomplete Navigation
     if chat_state.autocomplete.visible {
-        if keys.just_pressed(KeyCode::ArrowDown) {
-            if !chat_state.autocomplete.filtered_items.is_empty() {
-                chat_state.autocomplete.selected_index = (chat_state.autocomplete.selected_index
-                    + 1)
-                    % chat_state.autocomplete.filtered_items.len();
-            }
+        if actions.just_pressed(InputAction::AutocompleteNext)
+            && !chat_state.autocomplete.filtered_items.is_empty()
+        {
+            chat_state.autocomplete.selected_index = (chat_state.autocomplete.selected_index + 1)
+                % chat_state.autocomplete.filtered_items.len();
         }
 
-        if keys.just_pressed(KeyCode::ArrowUp) {
-            if !chat_state.autocomplete.filtered_items.is_empty() {
-                let len = chat_state.autocomplete.filtered_items.len();
-                chat_state.autocomplete.selected_index =
-                    (chat_state.autocomplete.selected_index + len - 1) % len;
-            }
+        if actions.just_pressed(InputAction::AutocompletePrevious)
+            && !chat_state.autocomplete.filtered_items.is_empty()
+        {
+            let len = chat_state.autocomplete.filtered_items.len();
+            chat_state.autocomplete.selected_index =
+                (chat_state.autocomplete.selected_index + len - 1) % len;
         }
 
-        if keys.just_pressed(KeyCode::Tab) {
+        if actions.just_pressed(InputAction::AutocompleteAccept) {
             // Autocomplete-Auswahl übernehmen
             if let Some(item) = chat_state
                 .autocomplete
@@ -302,6 +305,17 @@ fn handle_chat_input(
     }
 }
 
+/// Sendet die Nachrichten aus der Outbox, solange eine Verbindung besteht
+fn flush_chat_outbox(
+    mut chat_state: ResMut<ChatState>,
+    mut chat_writer: MessageWriter<ClientChat>,
+) {
+    if chat_state.outbox.is_empty() {
+        return;
+    }
+    chat_writer.write_batch(chat_state.outbox.drain(..).map(|text| ClientChat { text }));
+}
+
 /// Wendet eine Autocomplete-Auswahl auf den Input an
 fn apply_autocomplete(chat_state: &mut ChatState, item: &AutocompleteItem) {
     let trigger_pos = chat_state.autocomplete.trigger_position;
@@ -328,6 +342,7 @@ pub fn render_chat_ui(
     mut contexts: EguiContexts,
     mut chat_state: ResMut<ChatState>,
     identity: Option<Res<PlayerIdentity>>,
+    settings: Res<UserSettings>,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
@@ -350,7 +365,12 @@ pub fn render_chat_ui(
             .title_bar(false)
             .frame(
                 egui::Frame::window(&ctx.style())
-                    .fill(egui::Color32::from_rgba_premultiplied(0, 0, 0, 200))
+                    .fill(egui::Color32::from_rgba_premultiplied(
+                        0,
+                        0,
+                        0,
+                        settings.chat.background_opacity,
+                    ))
                     .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(100))),
             )
             .show(ctx, |ui| {
@@ -361,12 +381,17 @@ pub fn render_chat_ui(
                 }
 
                 // Chat-Verlauf
+                let highlight_name = if settings.chat.highlight_mentions {
+                    chat_state.own_player_name.as_str()
+                } else {
+                    ""
+                };
                 egui::ScrollArea::vertical()
                     .max_height(200.0)
                     .stick_to_bottom(chat_state.scroll_to_bottom)
                     .show(ui, |ui| {
                         for entry in &chat_state.messages {
-                            render_chat_message(ui, entry, &chat_state.own_player_name);
+                            render_chat_message(ui, entry, highlight_name);
                         }
                     });
 
@@ -409,13 +434,21 @@ pub fn render_chat_ui(
                     render_autocomplete_popup(ui, &mut chat_state);
                 }
             });
-    } else {
+    } else if settings.chat.show_hint {
         // Kleine Hinweisanzeige wenn Chat geschlossen ist
         egui::Area::new("chat_hint".into())
             .anchor(egui::Align2::LEFT_BOTTOM, egui::Vec2::new(10.0, -10.0))
             .show(ctx, |ui| {
+                let keys = settings
+                    .controls
+                    .bindings
+                    .get(InputAction::OpenChat)
+                    .iter()
+                    .map(|binding| binding.label())
+                    .collect::<Vec<_>>()
+                    .join(" oder ");
                 ui.label(
-                    egui::RichText::new("Drücke ENTER oder T zum Chatten")
+                    egui::RichText::new(format!("Drücke {keys} zum Chatten"))
                         .color(egui::Color32::from_rgba_premultiplied(200, 200, 200, 150))
                         .small(),
                 );
@@ -453,7 +486,7 @@ fn render_highlighted_text(ui: &mut egui::Ui, text: &str, own_name: &str) {
     for word in text.split_whitespace() {
         if word.starts_with(CHAT_MENTION_PREFIX) {
             // Mention gefunden
-            let is_own_mention = word == own_mention;
+            let is_own_mention = !own_name.is_empty() && word == own_mention;
             let color = if is_own_mention {
                 egui::Color32::from_rgb(255, 200, 50) // Hellorange für eigene Mentions
             } else {
@@ -484,7 +517,7 @@ fn update_autocomplete_ui(chat_state: &mut ChatState) {
     let input = &chat_state.input;
 
     // Prüfe ob wir im Autocomplete-Modus sein sollten
-    let last_trigger_pos = input.rfind(|c| c == CHAT_COMMAND_PREFIX || c == CHAT_MENTION_PREFIX);
+    let last_trigger_pos = input.rfind([CHAT_COMMAND_PREFIX, CHAT_MENTION_PREFIX]);
 
     if let Some(pos) = last_trigger_pos {
         // Prüfe ob es ein neuer Trigger ist (nach Leerzeichen oder am Anfang)
@@ -602,3 +635,287 @@ fn render_autocomplete_popup(ui: &mut egui::Ui, chat_state: &mut ChatState) {
             );
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::input::InputActionsPlugin,
+        bevy::{ecs::message::Messages, state::app::StatesPlugin, time::TimeUpdateStrategy},
+        chicken::protocols::{ChatErrorType, ClientChatHistoryRequest},
+        std::time::Duration,
+    };
+
+    /// Feste Frame-Dauer; entspricht dem Standard-`max_delta` von `Time<Virtual>`
+    const FRAME: Duration = Duration::from_millis(250);
+
+    /// Minimale Headless-App: keine Fenster, kein Netzwerk, nur ChatPlugin und die
+    /// Messages, die sonst vom Netzwerk-Protokoll registriert werden.
+    fn chat_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<UserSettings>()
+            .add_message::<ServerChat>()
+            .add_message::<ServerChatError>()
+            .add_message::<ServerChatAutocomplete>()
+            .add_message::<ServerChatHistoryResponse>()
+            .add_message::<ClientChat>()
+            .add_message::<ClientChatHistoryRequest>()
+            .insert_state(ClientConnectionStatus::Playing)
+            .add_plugins((InputActionsPlugin, ChatPlugin));
+        app.update();
+        app
+    }
+
+    /// Simuliert einen Tastendruck für genau einen Frame
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+    }
+
+    /// Lässt die App für die angegebene Zeit in festen Frames laufen
+    fn advance(app: &mut App, duration: Duration) {
+        for _ in 0..duration.as_millis() / FRAME.as_millis() {
+            app.update();
+        }
+    }
+
+    fn chat_state(app: &App) -> &ChatState {
+        app.world().resource::<ChatState>()
+    }
+
+    fn sent_messages(app: &mut App) -> Vec<String> {
+        app.world_mut()
+            .resource_mut::<Messages<ClientChat>>()
+            .drain()
+            .map(|msg| msg.text)
+            .collect()
+    }
+
+    fn server_chat(text: &str) -> ServerChat {
+        ServerChat {
+            sender_name: "Alice".to_string(),
+            sender_steam_id: None,
+            text: text.to_string(),
+            timestamp: None,
+        }
+    }
+
+    fn autocomplete_item(name: &str) -> AutocompleteItem {
+        AutocompleteItem {
+            display: name.to_string(),
+            replacement: name.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn requests_history_when_entering_playing() {
+        let app = chat_app();
+        let requests = app.world().resource::<Messages<ClientChatHistoryRequest>>();
+        assert_eq!(requests.len(), 1);
+        assert!(!chat_state(&app).history_loaded);
+    }
+
+    #[test]
+    fn history_response_is_applied() {
+        let mut app = chat_app();
+        app.world_mut().write_message(ServerChatHistoryResponse {
+            history: vec![server_chat("eins"), server_chat("zwei")],
+        });
+        app.update();
+
+        let state = chat_state(&app);
+        assert!(state.history_loaded);
+        assert_eq!(state.messages.len(), 2);
+        assert_eq!(state.messages[1].text, "zwei");
+    }
+
+    #[test]
+    fn history_after_reconnect_skips_known_messages() {
+        let mut app = chat_app();
+        let stamped = |text: &str, timestamp| ServerChat {
+            timestamp: Some(timestamp),
+            ..server_chat(text)
+        };
+        app.world_mut().write_message(stamped("eins", 1));
+        app.update();
+        app.world_mut().write_message(ServerChatHistoryResponse {
+            history: vec![stamped("eins", 1), stamped("zwei", 2)],
+        });
+        app.update();
+
+        let texts: Vec<_> = chat_state(&app)
+            .messages
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect();
+        assert_eq!(texts, ["eins", "zwei"]);
+    }
+
+    #[test]
+    fn live_messages_are_capped_at_history_size() {
+        let mut app = chat_app();
+        for i in 0..CHAT_CLIENT_HISTORY_SIZE + 5 {
+            app.world_mut().write_message(server_chat(&i.to_string()));
+        }
+        app.update();
+
+        let state = chat_state(&app);
+        assert_eq!(state.messages.len(), CHAT_CLIENT_HISTORY_SIZE);
+        assert_eq!(state.messages[0].text, "5");
+        assert!(state.scroll_to_bottom);
+    }
+
+    #[test]
+    fn error_message_expires_after_five_seconds() {
+        let mut app = chat_app();
+        app.world_mut().write_message(ServerChatError {
+            error_type: ChatErrorType::UnknownCommand,
+            message: "/foo".to_string(),
+        });
+        app.update();
+
+        let (text, _) = chat_state(&app).error_message.clone().expect("error set");
+        assert_eq!(text, "[Unbekannter Befehl] /foo");
+
+        advance(&mut app, Duration::from_secs(4));
+        assert!(chat_state(&app).error_message.is_some());
+
+        advance(&mut app, Duration::from_secs(1));
+        assert!(chat_state(&app).error_message.is_none());
+    }
+
+    #[test]
+    fn error_label_follows_lang_override() {
+        let mut app = chat_app();
+        app.insert_resource(LanguageOverride(Language::English));
+        app.world_mut().write_message(ServerChatError {
+            error_type: ChatErrorType::UnknownCommand,
+            message: "/foo".to_string(),
+        });
+        app.update();
+
+        let (text, _) = chat_state(&app).error_message.clone().expect("error set");
+        assert_eq!(text, "[Unknown command] /foo");
+    }
+
+    #[test]
+    fn enter_opens_chat_and_sends_input() {
+        let mut app = chat_app();
+        press(&mut app, KeyCode::Enter);
+        assert!(chat_state(&app).is_open);
+        assert!(chat_state(&app).has_focus);
+
+        app.world_mut().resource_mut::<ChatState>().input = "Hallo Welt".to_string();
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(sent_messages(&mut app), vec!["Hallo Welt".to_string()]);
+        assert!(chat_state(&app).input.is_empty());
+        assert!(chat_state(&app).is_open);
+    }
+
+    #[test]
+    fn enter_on_empty_input_closes_chat() {
+        let mut app = chat_app();
+        press(&mut app, KeyCode::KeyT);
+        assert!(chat_state(&app).is_open);
+
+        press(&mut app, KeyCode::Enter);
+        assert!(!chat_state(&app).is_open);
+        assert!(sent_messages(&mut app).is_empty());
+    }
+
+    #[test]
+    fn too_long_input_is_not_sent() {
+        let mut app = chat_app();
+        press(&mut app, KeyCode::Enter);
+        app.world_mut().resource_mut::<ChatState>().input = "x".repeat(CHAT_MESSAGE_MAX_LENGTH + 1);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(sent_messages(&mut app).is_empty());
+        assert_eq!(chat_state(&app).input.len(), CHAT_MESSAGE_MAX_LENGTH + 1);
+    }
+
+    #[test]
+    fn escape_closes_chat_and_clears_input() {
+        let mut app = chat_app();
+        press(&mut app, KeyCode::Enter);
+        {
+            let mut state = app.world_mut().resource_mut::<ChatState>();
+            state.input = "halb getippt".to_string();
+            state.autocomplete.visible = true;
+        }
+        press(&mut app, KeyCode::Escape);
+
+        let state = chat_state(&app);
+        assert!(!state.is_open);
+        assert!(!state.has_focus);
+        assert!(!state.autocomplete.visible);
+        assert!(state.input.is_empty());
+    }
+
+    #[test]
+    fn autocomplete_navigation_wraps_around() {
+        let mut app = chat_app();
+        press(&mut app, KeyCode::Enter);
+        {
+            let mut state = app.world_mut().resource_mut::<ChatState>();
+            state.autocomplete.visible = true;
+            state.autocomplete.filtered_items = vec![
+                autocomplete_item("a"),
+                autocomplete_item("b"),
+                autocomplete_item("c"),
+            ];
+        }
+
+        press(&mut app, KeyCode::ArrowUp);
+        assert_eq!(chat_state(&app).autocomplete.selected_index, 2);
+
+        press(&mut app, KeyCode::ArrowDown);
+        assert_eq!(chat_state(&app).autocomplete.selected_index, 0);
+
+        press(&mut app, KeyCode::ArrowDown);
+        assert_eq!(chat_state(&app).autocomplete.selected_index, 1);
+    }
+
+    #[test]
+    fn tab_applies_selected_autocomplete_item() {
+        let mut app = chat_app();
+        app.world_mut().write_message(ServerChatAutocomplete {
+            commands: vec![ChatCommandInfo {
+                command: "help".to_string(),
+                description: "Zeigt die Hilfe".to_string(),
+                usage: "/help".to_string(),
+            }],
+            players: vec![ChatPlayerInfo {
+                name: "Bob".to_string(),
+                steam_id: None,
+            }],
+        });
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(chat_state(&app).autocomplete.commands.len(), 1);
+        assert_eq!(chat_state(&app).autocomplete.players.len(), 1);
+
+        {
+            let mut state = app.world_mut().resource_mut::<ChatState>();
+            state.input = "hi @b".to_string();
+            update_autocomplete_ui(&mut state);
+            assert!(state.autocomplete.visible);
+            assert_eq!(state.autocomplete.trigger_char, Some(CHAT_MENTION_PREFIX));
+            assert_eq!(state.autocomplete.filtered_items.len(), 1);
+        }
+        press(&mut app, KeyCode::Tab);
+
+        let state = chat_state(&app);
+        assert_eq!(state.input, "hi @Bob ");
+        assert!(!state.autocomplete.visible);
+    }
+}
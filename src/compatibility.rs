@@ -0,0 +1,301 @@
+use {
+    crate::{
+        address::{JoinAddress, ResolveState},
+        config::{PROTOCOL_HASH, VERSION},
+        connection::{self, ConnectionAttempt},
+        server_query::{QUERY_TIMEOUT, ServerInfo, ServerQueries},
+        settings::UserSettings,
+    },
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::{
+        network::client::SetClientTarget,
+        states::{
+            events::menu::multiplayer::SetJoinGame,
+            states::menu::multiplayer::MultiplayerMenuScreen,
+        },
+    },
+    std::{net::SocketAddr, time::Instant},
+};
+
+/// Plugin für den Versions-Check vor dem Beitreten.
+///
+/// "Join" löst [`RequestJoin`] statt direkt `SetJoinGame::Confirm` aus. Zuerst wird
+/// die Adresse aufgelöst und die [`ServerInfo`] abgefragt; meldet der Server ein
+/// anderes Protokoll, wird der Beitritt mit Erklärung abgelehnt.
+///
+/// chicken bietet keinen Platz für die Version im Verbindungsaufbau, geprüft wird
+/// daher nur über den Info-Dienst. Antwortet der Server nicht (z.B. ein dedizierter
+/// Server ohne Info-Dienst), fragt der Client nach, ob trotzdem verbunden werden
+/// soll. Automatisches Wiederverbinden und [`SkipUnverifiedPrompt`] verbinden ohne
+/// Nachfrage.
+pub struct CompatibilityPlugin;
+
+impl Plugin for CompatibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JoinCheck>()
+            .add_observer(on_request_join)
+            .add_systems(
+                Update,
+                resolve_join_check.run_if(in_state(MultiplayerMenuScreen::JoinGame)),
+            )
+            .add_systems(OnExit(MultiplayerMenuScreen::JoinGame), reset_join_check);
+    }
+}
+
+/// Ob ein Server zu diesem Client passt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    /// Andere Version, aber gleiches Netzwerkprotokoll
+    VersionDiffers,
+    Incompatible,
+}
+
+/// Vergleicht die von einem Server gemeldete Version mit diesem Client
+pub fn check(version: &str, protocol: &str) -> Compatibility {
+    if protocol != PROTOCOL_HASH {
+        Compatibility::Incompatible
+    } else if version != VERSION {
+        Compatibility::VersionDiffers
+    } else {
+        Compatibility::Compatible
+    }
+}
+
+/// Erklärung für Spieler, falls der Server nicht genau zu diesem Client passt
+pub fn mismatch_text(info: &ServerInfo) -> Option<String> {
+    match info.compatibility() {
+        Compatibility::Compatible => None,
+        Compatibility::VersionDiffers => Some(format!(
+            "Server runs version {}, this client is version {VERSION}. They can still play together.",
+            info.version
+        )),
+        Compatibility::Incompatible if info.protocol.is_empty() => Some(format!(
+            "Server runs version {}, which is too old for this client (version {VERSION}). \
+             Update the server to play together.",
+            info.version
+        )),
+        Compatibility::Incompatible => Some(format!(
+            "Server runs version {} (protocol {}), this client is version {VERSION} \
+             (protocol {PROTOCOL_HASH}). Both need the same protocol to play together.",
+            info.version, info.protocol
+        )),
+    }
+}
+
+/// Ohne Nachfrage verbinden, wenn ein Server den Versions-Check nicht beantwortet,
+/// z.B. für Headless-Clients ohne UI
+#[derive(Resource, Debug, Default)]
+pub struct SkipUnverifiedPrompt;
+
+/// Beitritt zu einer Adresse, nachdem deren Version geprüft wurde
+#[derive(Event, Debug, Clone)]
+pub struct RequestJoin {
+    pub address: String,
+}
+
+/// Laufender bzw. abgelehnter Beitritt in "Join Game"
+#[derive(Resource, Debug, Default)]
+pub enum JoinCheck {
+    #[default]
+    Idle,
+    /// Warte auf Auflösung und Server-Info
+    Checking { address: String, since: Instant },
+    /// Der Server hat den Versions-Check nicht beantwortet; der Spieler entscheidet
+    Unverified {
+        address: String,
+        resolved: SocketAddr,
+        /// "Join anyway" wurde gewählt
+        accepted: bool,
+    },
+    /// Beitritt wurde abgelehnt, z.B. wegen inkompatibler Version
+    Rejected { address: String, reason: String },
+}
+
+fn on_request_join(
+    event: On<RequestJoin>,
+    mut check: ResMut<JoinCheck>,
+    mut join: ResMut<JoinAddress>,
+    mut queries: ResMut<ServerQueries>,
+    settings: Res<UserSettings>,
+) {
+    let address = event.address.trim().to_string();
+    let default_port = settings.network.default_port;
+    if join.input.trim() != address || matches!(join.state, ResolveState::Failed(_)) {
+        join.set_input(address.clone(), default_port);
+    }
+    queries.watch(&address, default_port);
+    *check = JoinCheck::Checking {
+        address,
+        since: Instant::now(),
+    };
+}
+
+fn resolve_join_check(
+    mut commands: Commands,
+    mut check: ResMut<JoinCheck>,
+    join: Res<JoinAddress>,
+    queries: Res<ServerQueries>,
+    attempt: Option<Res<ConnectionAttempt>>,
+    skip_prompt: Option<Res<SkipUnverifiedPrompt>>,
+) {
+    let ask_if_unverified =
+        skip_prompt.is_none() && !attempt.is_some_and(|attempt| attempt.automatic);
+    let next = match &mut *check {
+        JoinCheck::Checking { address, since } => check_server(
+            &mut commands,
+            address,
+            *since,
+            &join,
+            &queries,
+            ask_if_unverified,
+        ),
+        JoinCheck::Unverified {
+            resolved,
+            accepted: true,
+            ..
+        } => Some(confirm_join(&mut commands, *resolved)),
+        JoinCheck::Idle | JoinCheck::Unverified { .. } | JoinCheck::Rejected { .. } => None,
+    };
+    if let Some(next) = next {
+        *check = next;
+    }
+}
+
+/// Wertet Auflösung und Server-Info aus; `None` = weiter warten
+fn check_server(
+    commands: &mut Commands,
+    address: &str,
+    since: Instant,
+    join: &JoinAddress,
+    queries: &ServerQueries,
+    ask_if_unverified: bool,
+) -> Option<JoinCheck> {
+    let resolved = match &join.state {
+        ResolveState::Resolved(resolved) => *resolved,
+        ResolveState::Failed(err) => {
+            return Some(JoinCheck::Rejected {
+                address: address.to_string(),
+                reason: err.to_string(),
+            });
+        }
+        ResolveState::Empty | ResolveState::Resolving(_) => return None,
+    };
+
+    match queries.info(address) {
+        Some(info) if info.compatibility() == Compatibility::Incompatible => {
+            let reason = mismatch_text(info).unwrap_or_default();
+            warn!("Refusing to join {address}: {reason}");
+            return Some(JoinCheck::Rejected {
+                address: address.to_string(),
+                reason,
+            });
+        }
+        Some(_) => {}
+        None if queries.get(address).is_some_and(|state| state.is_pending())
+            || since.elapsed() <= QUERY_TIMEOUT =>
+        {
+            return None;
+        }
+        // Server ohne Info-Dienst (oder hinter einer Firewall)
+        None if ask_if_unverified => {
+            info!("{address} did not answer the version check");
+            return Some(JoinCheck::Unverified {
+                address: address.to_string(),
+                resolved,
+                accepted: false,
+            });
+        }
+        None => info!("{address} did not answer the version check, joining anyway"),
+    }
+    Some(confirm_join(commands, resolved))
+}
+
+/// Setzt das Ziel direkt vor dem Confirm, damit chicken die aufgelöste Adresse nutzt
+fn confirm_join(commands: &mut Commands, resolved: SocketAddr) -> JoinCheck {
+    commands.queue(SetClientTarget {
+        input: resolved.to_string(),
+    });
+    commands.trigger(SetJoinGame::Confirm);
+    JoinCheck::Idle
+}
+
+fn reset_join_check(mut check: ResMut<JoinCheck>) {
+    *check = JoinCheck::Idle;
+}
+
+// --- UI ---
+
+/// Zeigt den laufenden Check, die Rückfrage bzw. den Ablehnungsgrund als Modal
+pub fn render_join_check(
+    ui: &mut egui::Ui,
+    check: &mut JoinCheck,
+    attempt: Option<&ConnectionAttempt>,
+) {
+    let mut next = None;
+    match check {
+        JoinCheck::Idle => return,
+        JoinCheck::Checking { address, .. } => {
+            egui::Modal::new(egui::Id::new("join_check")).show(ui.ctx(), |ui| {
+                ui.heading(format!("Joining {address}"));
+                if let Some(attempt) = attempt {
+                    connection::render_stages(ui, attempt);
+                }
+                if ui.button("Cancel").clicked() {
+                    next = Some(JoinCheck::Idle);
+                }
+            });
+        }
+        JoinCheck::Unverified {
+            address, accepted, ..
+        } => {
+            egui::Modal::new(egui::Id::new("join_check")).show(ui.ctx(), |ui| {
+                ui.heading(format!("Could not check {address}"));
+                ui.label(
+                    "The server did not answer the version check. It may be a dedicated \
+                     server without server info, or its query port may be blocked. \
+                     If it runs an incompatible version, joining will fail.",
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Join anyway").clicked() {
+                        *accepted = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        next = Some(JoinCheck::Idle);
+                    }
+                });
+            });
+        }
+        JoinCheck::Rejected { address, reason } => {
+            egui::Modal::new(egui::Id::new("join_check")).show(ui.ctx(), |ui| {
+                ui.heading(format!("Cannot join {address}"));
+                ui.label(reason.as_str());
+                if ui.button("OK").clicked() {
+                    next = Some(JoinCheck::Idle);
+                }
+            });
+        }
+    }
+    if let Some(next) = next {
+        *check = next;
+    }
+}
+
+/// Version dieses Clients für die Fußzeile von "Join Game"
+pub fn client_version_text() -> String {
+    format!("Client version {VERSION} · protocol {PROTOCOL_HASH}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn servers_are_compared_by_protocol_then_version() {
+        assert_eq!(check(VERSION, PROTOCOL_HASH), Compatibility::Compatible);
+        assert_eq!(check("0.0.0", PROTOCOL_HASH), Compatibility::VersionDiffers);
+        assert_eq!(check(VERSION, "0000"), Compatibility::Incompatible);
+        assert_eq!(check(VERSION, ""), Compatibility::Incompatible);
+    }
+}
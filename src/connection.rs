@@ -0,0 +1,393 @@
+use {
+    crate::{
+        address::{JoinAddress, ResolveState},
+        chat::ChatState,
+        compatibility::{JoinCheck, RequestJoin},
+    },
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::{
+        protocols::ServerChatAutocomplete,
+        states::{
+            events::{
+                menu::multiplayer::{SetJoinGame, SetMultiplayerMenu},
+                session::SetPauseMenu,
+            },
+            states::{
+                app::AppScope,
+                menu::{main::MainMenuScreen, multiplayer::MultiplayerMenuScreen},
+                session::{ClientConnectionStatus, SessionType},
+            },
+        },
+    },
+    std::time::{Duration, Instant},
+};
+
+/// Ohne Verbindung nach dieser Zeit wird der Versuch abgebrochen
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Plugin für den Verbindungsaufbau zu einem Server.
+///
+/// Verfolgt einen Beitritt von [`RequestJoin`] bis die Chat-History geladen ist
+/// und zeigt die Stufen samt verstrichener Zeit an. Endet die Session vorher, bleibt
+/// der Versuch mit Fehlergrund stehen, bis der Spieler "Retry" oder "Back" wählt.
+pub struct ConnectionPlugin;
+
+impl Plugin for ConnectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_request_join)
+            .add_observer(on_join_confirm)
+            .add_systems(
+                Update,
+                (
+                    track_join_check.run_if(in_state(AppScope::Menu)),
+                    (track_world_sync, track_connection)
+                        .chain()
+                        .run_if(in_state(AppScope::Session))
+                        .run_if(in_state(SessionType::Client)),
+                    navigate_after_attempt.run_if(in_state(AppScope::Menu)),
+                )
+                    .run_if(resource_exists::<ConnectionAttempt>),
+            )
+            .add_systems(
+                OnEnter(AppScope::Menu),
+                detect_failed_attempt.run_if(resource_exists::<ConnectionAttempt>),
+            );
+    }
+}
+
+/// Stufen eines Beitritts in der Reihenfolge, in der sie durchlaufen werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConnectionStage {
+    Resolving,
+    Connecting,
+    SyncingWorld,
+    LoadingChat,
+}
+
+impl ConnectionStage {
+    pub const ALL: [ConnectionStage; 4] = [
+        ConnectionStage::Resolving,
+        ConnectionStage::Connecting,
+        ConnectionStage::SyncingWorld,
+        ConnectionStage::LoadingChat,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionStage::Resolving => "Resolving address",
+            ConnectionStage::Connecting => "Connecting",
+            ConnectionStage::SyncingWorld => "Syncing world",
+            ConnectionStage::LoadingChat => "Loading chat history",
+        }
+    }
+}
+
+/// Was nach einem abgebrochenen oder fehlgeschlagenen Versuch passieren soll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AfterAttempt {
+    /// Zurück zu "Join Game"
+    Back,
+    /// Zurück zu "Join Game" und erneut beitreten
+    Retry,
+}
+
+/// Laufender Beitritt; wird entfernt, sobald der Client spielbereit ist
+#[derive(Resource, Debug)]
+pub struct ConnectionAttempt {
+    pub address: String,
+    pub started: Instant,
+    pub stage: ConnectionStage,
+    /// Zeitpunkt von `SetJoinGame::Confirm`
+    confirmed: Option<Instant>,
+    /// Der Server hat in diesem Versuch die ersten Session-Daten geschickt
+    world_synced: bool,
+    pub failure: Option<String>,
+    after: Option<AfterAttempt>,
+    /// Vom automatischen Wiederverbinden gestartet; Fehler zeigt dann kein Modal
+    pub automatic: bool,
+}
+
+impl ConnectionAttempt {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            started: Instant::now(),
+            stage: ConnectionStage::Resolving,
+            confirmed: None,
+            world_synced: false,
+            failure: None,
+            after: None,
+            automatic: false,
+        }
+    }
+
+    /// Automatischer Versuch: navigiert zu "Join Game" und tritt dort erneut bei
+    pub fn automatic(address: String) -> Self {
+        Self {
+            after: Some(AfterAttempt::Retry),
+            automatic: true,
+            ..Self::new(address)
+        }
+    }
+
+    fn fail(&mut self, reason: String) {
+        if self.failure.is_none() {
+            warn!("Joining {} failed: {reason}", self.address);
+            self.failure = Some(reason);
+        }
+    }
+}
+
+fn on_request_join(
+    event: On<RequestJoin>,
+    mut commands: Commands,
+    previous: Option<Res<ConnectionAttempt>>,
+) {
+    let address = event.address.trim().to_string();
+    let automatic =
+        previous.is_some_and(|previous| previous.automatic && previous.address == address);
+    commands.insert_resource(ConnectionAttempt {
+        automatic,
+        ..ConnectionAttempt::new(address)
+    });
+}
+
+fn on_join_confirm(
+    event: On<SetJoinGame>,
+    mut commands: Commands,
+    attempt: Option<ResMut<ConnectionAttempt>>,
+    join: Res<JoinAddress>,
+) {
+    if !matches!(*event, SetJoinGame::Confirm) {
+        return;
+    }
+    match attempt {
+        Some(mut attempt) => {
+            attempt.stage = ConnectionStage::Connecting;
+            attempt.confirmed = Some(Instant::now());
+        }
+        None => {
+            let mut attempt = ConnectionAttempt::new(join.input.trim().to_string());
+            attempt.stage = ConnectionStage::Connecting;
+            attempt.confirmed = Some(Instant::now());
+            commands.insert_resource(attempt);
+        }
+    }
+}
+
+/// Stufen vor dem Confirm ergeben sich aus der Adressauflösung und dem Join-Check
+fn track_join_check(
+    mut attempt: ResMut<ConnectionAttempt>,
+    check: Res<JoinCheck>,
+    join: Res<JoinAddress>,
+) {
+    if let Some(confirmed) = attempt.confirmed {
+        // chicken wechselt beim Verbinden in die Session; bleibt das aus, ist etwas schiefgelaufen
+        if confirmed.elapsed() > CONNECT_TIMEOUT {
+            let reason = format!(
+                "The connection to {} could not be started.",
+                attempt.address
+            );
+            attempt.fail(reason);
+        }
+        return;
+    }
+    if matches!(*check, JoinCheck::Checking { .. })
+        && matches!(join.state, ResolveState::Resolving(_))
+    {
+        attempt.stage = ConnectionStage::Resolving;
+    }
+}
+
+/// Die Spielerliste fürs Autocomplete kommt mit den ersten Session-Daten vom Server
+fn track_world_sync(
+    mut attempt: ResMut<ConnectionAttempt>,
+    mut autocomplete: MessageReader<ServerChatAutocomplete>,
+) {
+    if !autocomplete.is_empty() {
+        autocomplete.clear();
+        attempt.world_synced = true;
+    }
+}
+
+/// Stufen nach dem Confirm ergeben sich aus dem Verbindungsstatus und dem Chat
+fn track_connection(
+    mut commands: Commands,
+    mut attempt: ResMut<ConnectionAttempt>,
+    status: Res<State<ClientConnectionStatus>>,
+    chat: Res<ChatState>,
+) {
+    if attempt.failure.is_some() || attempt.after.is_some() {
+        return;
+    }
+
+    attempt.stage = if *status.get() != ClientConnectionStatus::Playing {
+        ConnectionStage::Connecting
+    } else if !attempt.world_synced {
+        ConnectionStage::SyncingWorld
+    } else if !chat.history_loaded {
+        ConnectionStage::LoadingChat
+    } else {
+        info!(
+            "Joined {} after {:.1}s",
+            attempt.address,
+            attempt.started.elapsed().as_secs_f32()
+        );
+        commands.remove_resource::<ConnectionAttempt>();
+        return;
+    };
+
+    if attempt.stage == ConnectionStage::Connecting
+        && attempt
+            .confirmed
+            .is_some_and(|confirmed| confirmed.elapsed() > CONNECT_TIMEOUT)
+    {
+        let reason = format!(
+            "{} did not respond within {} seconds. Check that the server is running and reachable.",
+            attempt.address,
+            CONNECT_TIMEOUT.as_secs()
+        );
+        attempt.fail(reason);
+        commands.trigger(SetPauseMenu::Exit);
+    }
+}
+
+/// Zurück im Menü, bevor der Client spielbereit war: der Versuch ist gescheitert
+fn detect_failed_attempt(mut attempt: ResMut<ConnectionAttempt>) {
+    if attempt.confirmed.is_none() || attempt.after.is_some() {
+        return;
+    }
+    let reason = match attempt.stage {
+        ConnectionStage::Connecting => format!(
+            "Could not connect to {}. The server may be offline, full or running a different version.",
+            attempt.address
+        ),
+        _ => format!(
+            "The connection to {} was lost while joining.",
+            attempt.address
+        ),
+    };
+    attempt.fail(reason);
+}
+
+/// Navigiert nach "Retry" bzw. "Back" Schritt für Schritt zurück zu "Join Game"
+fn navigate_after_attempt(
+    mut commands: Commands,
+    attempt: Res<ConnectionAttempt>,
+    menu: Res<State<MainMenuScreen>>,
+    multiplayer: Option<Res<State<MultiplayerMenuScreen>>>,
+) {
+    let Some(after) = attempt.after else {
+        return;
+    };
+    match (menu.get(), multiplayer.map(|state| *state.get())) {
+        (MainMenuScreen::Multiplayer, Some(MultiplayerMenuScreen::JoinGame)) => {
+            // Bei "Retry" ersetzt der neue Versuch diesen
+            match after {
+                AfterAttempt::Retry => commands.trigger(RequestJoin {
+                    address: attempt.address.clone(),
+                }),
+                AfterAttempt::Back => commands.remove_resource::<ConnectionAttempt>(),
+            }
+        }
+        (MainMenuScreen::Multiplayer, Some(MultiplayerMenuScreen::Overview)) => {
+            commands.trigger(SetMultiplayerMenu::JoinGame);
+        }
+        (MainMenuScreen::Overview, _) => commands.trigger(SetMultiplayerMenu::Overview),
+        // Untermenü wird gerade gewechselt
+        (MainMenuScreen::Multiplayer, None) => {}
+        // Anderes Untermenü offen – dort nicht eingreifen
+        _ => {
+            commands.remove_resource::<ConnectionAttempt>();
+        }
+    }
+}
+
+// --- UI ---
+
+/// Rendert die Stufenliste mit verstrichener Zeit
+pub fn render_stages(ui: &mut egui::Ui, attempt: &ConnectionAttempt) {
+    for stage in ConnectionStage::ALL {
+        ui.horizontal(|ui| {
+            if stage < attempt.stage {
+                ui.label(format!("✔ {}", stage.label()));
+            } else if stage == attempt.stage && attempt.failure.is_none() {
+                ui.add(egui::Spinner::new());
+                ui.strong(stage.label());
+            } else if stage == attempt.stage {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 100, 100),
+                    format!("✖ {}", stage.label()),
+                );
+            } else {
+                ui.weak(stage.label());
+            }
+        });
+    }
+    ui.label(format!(
+        "Elapsed: {:.0}s",
+        attempt.started.elapsed().as_secs_f32()
+    ));
+    // Damit Spinner und Zeit auch ohne Eingaben weiterlaufen
+    ui.ctx().request_repaint_after(Duration::from_millis(250));
+}
+
+/// Verbindungsbildschirm während der Client-Session. Gibt `true` zurück, wenn der
+/// Spieler abbrechen will.
+pub fn render_connection_screen(ui: &mut egui::Ui, attempt: &mut ConnectionAttempt) -> bool {
+    ui.heading(format!("Joining {}", attempt.address));
+    ui.separator();
+    render_stages(ui, attempt);
+    ui.separator();
+    if attempt.failure.is_some() || attempt.after.is_some() {
+        ui.label("Returning to the menu…");
+        return false;
+    }
+    if ui.button("Cancel").clicked() {
+        info!("Cancelled joining {}", attempt.address);
+        attempt.after = Some(AfterAttempt::Back);
+        return true;
+    }
+    false
+}
+
+/// Fehlermeldung mit "Retry"/"Back" im Menü nach einem gescheiterten Versuch
+pub fn render_connection_failure(ctx: &egui::Context, attempt: &mut ConnectionAttempt) {
+    let Some(reason) = attempt
+        .failure
+        .as_ref()
+        .filter(|_| attempt.after.is_none() && !attempt.automatic)
+    else {
+        return;
+    };
+    let mut after = None;
+    egui::Modal::new(egui::Id::new("connection_failure")).show(ctx, |ui| {
+        ui.heading("Connection failed");
+        ui.label(reason.as_str());
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Retry").clicked() {
+                after = Some(AfterAttempt::Retry);
+            }
+            if ui.button("Back").clicked() {
+                after = Some(AfterAttempt::Back);
+            }
+        });
+    });
+    attempt.after = after;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_first_failure_is_kept() {
+        let mut attempt = ConnectionAttempt::new("fos.lan:8080".to_string());
+        assert_eq!(attempt.stage, ConnectionStage::Resolving);
+        attempt.fail("timed out".to_string());
+        attempt.fail("connection lost".to_string());
+        assert_eq!(attempt.failure.as_deref(), Some("timed out"));
+    }
+}
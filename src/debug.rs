@@ -1,3 +1,5 @@
+use crate::input::{ActionState, InputAction};
+use crate::settings::UserSettings;
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use chicken::states::states::{
@@ -10,10 +12,22 @@ pub struct DebugStatePlugin;
 
 impl Plugin for DebugStatePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(EguiPrimaryContextPass, ui_debug_state_overlay);
+        app.add_systems(
+            EguiPrimaryContextPass,
+            ui_debug_state_overlay
+                .run_if(|settings: Res<UserSettings>| settings.interface.show_state_overlay),
+        )
+        .add_systems(Update, toggle_debug_overlay);
     }
 }
 
+fn toggle_debug_overlay(actions: Res<ActionState>, mut settings: ResMut<UserSettings>) {
+    if actions.just_pressed(InputAction::ToggleDebugOverlay) {
+        settings.interface.show_state_overlay = !settings.interface.show_state_overlay;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn ui_debug_state_overlay(
     mut egui: EguiContexts,
     // App Scope
@@ -0,0 +1,375 @@
+use {
+    bevy::prelude::*,
+    bevy_egui::egui,
+    std::{fmt, path::PathBuf},
+};
+
+/// URL-Schema der Einladungslinks
+pub const SCHEME: &str = "fos";
+
+/// Plugin für Einladungslinks (`fos://join/<host>:<port>`).
+///
+/// Der Client nimmt den Link als erstes Argument entgegen. Läuft bereits eine
+/// Instanz, wird er über einen lokalen Socket an diese weitergereicht und dort als
+/// [`OpenLink`] ausgelöst. Der Socket wird in einem eigenen Thread gelesen, damit ein
+/// langsamer Sender keinen Frame aufhält. Nur unter Unix; andernorts startet jeder
+/// Link einen eigenen Client.
+pub struct DeepLinkPlugin;
+
+impl Plugin for DeepLinkPlugin {
+    #[cfg(unix)]
+    fn build(&self, app: &mut App) {
+        match instance::listen(&socket_path()).and_then(instance::spawn_receiver) {
+            Ok(links) => {
+                app.insert_resource(links)
+                    .add_systems(Update, instance::receive_links);
+            }
+            Err(err) => warn!("Could not open instance socket, links start a new client: {err}"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn build(&self, _app: &mut App) {}
+}
+
+/// Ein Link soll geöffnet werden, z.B. weil er an diese Instanz weitergereicht wurde
+#[derive(Event, Debug, Clone)]
+pub struct OpenLink(pub DeepLink);
+
+/// Hinweise zu geöffneten Links, bis der Spieler sie bestätigt
+#[derive(Resource, Debug, Default)]
+pub struct LinkNotices(pub Vec<String>);
+
+/// Grund, warum ein Link nicht verwendet werden kann
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLinkError {
+    Scheme,
+    /// Unbekannte Aktion, z.B. `fos://spectate/...`
+    Action(String),
+    MissingAddress,
+    Encoding,
+}
+
+impl fmt::Display for DeepLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeepLinkError::Scheme => write!(f, "Link must start with {SCHEME}://"),
+            DeepLinkError::Action(action) => write!(f, "Unknown link action '{action}'"),
+            DeepLinkError::MissingAddress => write!(f, "Link has no server address"),
+            DeepLinkError::Encoding => write!(f, "Link contains invalid escape sequences"),
+        }
+    }
+}
+
+impl std::error::Error for DeepLinkError {}
+
+/// Einladung zu einem Server. Die Adresse wird erst beim Beitreten aufgelöst.
+///
+/// chicken kann beim Verbinden kein Passwort übergeben. Von `?password=...` wird
+/// daher nur vermerkt, dass es da war, um den Spieler darauf hinzuweisen; der Wert
+/// selbst wird verworfen und landet so auch nicht in den Logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepLink {
+    pub address: String,
+    pub has_password: bool,
+}
+
+impl DeepLink {
+    pub fn parse(uri: &str) -> Result<Self, DeepLinkError> {
+        let uri = uri.trim();
+        let rest = uri
+            .split_once("://")
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or(DeepLinkError::Scheme)?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (action, address) = path.split_once('/').unwrap_or((path, ""));
+        if !action.eq_ignore_ascii_case("join") {
+            return Err(DeepLinkError::Action(action.to_string()));
+        }
+
+        let address = percent_decode(address.trim_end_matches('/'))?;
+        if address.trim().is_empty() {
+            return Err(DeepLinkError::MissingAddress);
+        }
+        let has_password = query
+            .split('&')
+            .any(|pair| pair.split('=').next() == Some("password"));
+        Ok(Self {
+            address,
+            has_password,
+        })
+    }
+
+    /// Hinweis für den Spieler, falls der Link mehr verlangt, als möglich ist
+    pub fn notice(&self) -> Option<String> {
+        self.has_password.then(|| {
+            format!(
+                "The invite to {} contains a password. Password-protected servers are not \
+                 supported yet, so the client joins without it.",
+                self.address
+            )
+        })
+    }
+}
+
+/// Ohne den Wert des Passworts; der Empfänger erfährt nur, dass eines angegeben war
+impl fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{SCHEME}://join/{}", percent_encode(&self.address))?;
+        if self.has_password {
+            write!(f, "?password")?;
+        }
+        Ok(())
+    }
+}
+
+/// Dekodiert `%XX`
+fn percent_decode(input: &str) -> Result<String, DeepLinkError> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut iter = input.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'%' => {
+                let hex = [
+                    iter.next().ok_or(DeepLinkError::Encoding)?,
+                    iter.next().ok_or(DeepLinkError::Encoding)?,
+                ];
+                let hex = std::str::from_utf8(&hex).map_err(|_| DeepLinkError::Encoding)?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| DeepLinkError::Encoding)?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| DeepLinkError::Encoding)
+}
+
+fn percent_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Socket der laufenden Instanz, bevorzugt im Laufzeitverzeichnis des Benutzers
+fn socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .unwrap_or_else(crate::paths::data_dir)
+        .join("fos-client.sock")
+}
+
+/// Reicht den Link an eine laufende Instanz weiter. Gibt `true` zurück, wenn diese
+/// ihn angenommen hat und dieser Prozess sich beenden kann.
+pub fn forward_to_running_instance(link: &DeepLink) -> bool {
+    #[cfg(unix)]
+    {
+        instance::forward(&socket_path(), link)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = link;
+        false
+    }
+}
+
+#[cfg(unix)]
+mod instance {
+    use {
+        super::{DeepLink, OpenLink},
+        bevy::prelude::*,
+        std::{
+            fs,
+            io::{self, BufRead, BufReader, Read, Write},
+            os::unix::net::{UnixListener, UnixStream},
+            path::Path,
+            sync::{
+                Mutex,
+                mpsc::{self, Receiver},
+            },
+            thread,
+            time::Duration,
+        },
+    };
+
+    /// Obergrenze für einen weitergereichten Link
+    const MAX_LINK_BYTES: u64 = 4096;
+
+    /// Vom Socket-Thread empfangene Links
+    #[derive(Resource)]
+    pub struct ForwardedLinks(Mutex<Receiver<DeepLink>>);
+
+    pub fn listen(path: &Path) -> io::Result<UnixListener> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let listener = match UnixListener::bind(path) {
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => {
+                // Antwortet niemand, ist der Socket von einem abgestürzten Client übrig
+                if UnixStream::connect(path).is_ok() {
+                    return Err(err);
+                }
+                fs::remove_file(path)?;
+                UnixListener::bind(path)?
+            }
+            result => result?,
+        };
+        Ok(listener)
+    }
+
+    /// Nimmt Verbindungen in einem eigenen Thread an und reicht gültige Links weiter
+    pub fn spawn_receiver(listener: UnixListener) -> io::Result<ForwardedLinks> {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("instance-socket".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            warn!("Instance socket failed: {err}");
+                            return;
+                        }
+                    };
+                    for link in read_links(stream) {
+                        // Der Empfänger fehlt erst, wenn die App beendet wird
+                        if sender.send(link).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })?;
+        Ok(ForwardedLinks(Mutex::new(receiver)))
+    }
+
+    fn read_links(stream: UnixStream) -> Vec<DeepLink> {
+        // Der Sender schreibt eine Zeile und beendet sich; länger wird nicht gewartet
+        if stream
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .is_err()
+        {
+            return Vec::new();
+        }
+        BufReader::new(stream.take(MAX_LINK_BYTES))
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| {
+                DeepLink::parse(&line)
+                    .inspect_err(|err| warn!("Ignoring forwarded link: {err}"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    pub fn forward(path: &Path, link: &DeepLink) -> bool {
+        let Ok(mut stream) = UnixStream::connect(path) else {
+            return false;
+        };
+        match writeln!(stream, "{link}") {
+            Ok(()) => {
+                info!("Forwarded {link} to the running client");
+                true
+            }
+            Err(err) => {
+                warn!("Could not forward link to the running client: {err}");
+                false
+            }
+        }
+    }
+
+    pub fn receive_links(mut commands: Commands, links: Res<ForwardedLinks>) {
+        let Ok(receiver) = links.0.lock() else {
+            return;
+        };
+        for link in receiver.try_iter() {
+            info!("Received link {link}");
+            commands.trigger(OpenLink(link));
+        }
+    }
+}
+
+// --- UI ---
+
+/// Zeigt den ältesten offenen Hinweis, bis der Spieler ihn bestätigt
+pub fn render_link_notices(ctx: &egui::Context, notices: &mut LinkNotices) {
+    let Some(notice) = notices.0.first() else {
+        return;
+    };
+    let mut dismissed = false;
+    egui::Window::new("Invite link")
+        .anchor(egui::Align2::RIGHT_TOP, [-16.0, 16.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(notice);
+            dismissed = ui.button("OK").clicked();
+        });
+    if dismissed {
+        notices.0.remove(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(address: &str) -> Result<DeepLink, DeepLinkError> {
+        Ok(DeepLink {
+            address: address.to_string(),
+            has_password: false,
+        })
+    }
+
+    #[test]
+    fn join_links_are_parsed() {
+        assert_eq!(
+            DeepLink::parse("fos://join/fos.lan:9000"),
+            link("fos.lan:9000")
+        );
+        let with_password = DeepLink::parse("FOS://join/fos.lan:9000/?password=geheim%20123&x=1");
+        assert_eq!(
+            with_password,
+            Ok(DeepLink {
+                has_password: true,
+                ..link("fos.lan:9000").unwrap()
+            })
+        );
+        assert!(!with_password.unwrap().to_string().contains("geheim"));
+        assert_eq!(
+            DeepLink::parse("fos://join/%5B::1%5D:9000"),
+            link("[::1]:9000")
+        );
+
+        assert_eq!(
+            DeepLink::parse("https://join/fos.lan"),
+            Err(DeepLinkError::Scheme)
+        );
+        assert_eq!(
+            DeepLink::parse("fos://host/fos.lan"),
+            Err(DeepLinkError::Action("host".to_string()))
+        );
+        assert_eq!(
+            DeepLink::parse("fos://join/"),
+            Err(DeepLinkError::MissingAddress)
+        );
+        assert_eq!(
+            DeepLink::parse("fos://join/fos.lan%G1"),
+            Err(DeepLinkError::Encoding)
+        );
+    }
+
+    #[test]
+    fn links_survive_forwarding() {
+        for has_password in [false, true] {
+            let original = DeepLink {
+                address: "[::1]:9000".to_string(),
+                has_password,
+            };
+            assert_eq!(DeepLink::parse(&original.to_string()), Ok(original));
+        }
+    }
+}
@@ -0,0 +1,408 @@
+use {
+    crate::{
+        FOSClientPlugin,
+        chat::ChatState,
+        compatibility::SkipUnverifiedPrompt,
+        launch::{LaunchAction, LaunchArgs, LaunchPlugin, RunLaunchAction},
+        reconnect::Reconnecting,
+    },
+    bevy::{
+        app::ScheduleRunnerPlugin, input::InputPlugin, log::LogPlugin, prelude::*,
+        state::app::StatesPlugin,
+    },
+    chicken::{
+        protocols::ServerChat,
+        states::{
+            events::session::SetPauseMenu,
+            states::{app::AppScope, session::ClientConnectionStatus},
+        },
+    },
+    std::{
+        collections::VecDeque,
+        fmt, fs,
+        time::{Duration, Instant},
+    },
+};
+
+/// Bildrate ohne Fenster; hält die CPU-Last vieler Bots gering
+pub const HEADLESS_FRAME: Duration = Duration::from_micros(16_667);
+
+/// Standard-Wartezeit von `wait-connected`
+pub const CONNECT_WAIT: Duration = Duration::from_secs(30);
+
+/// Ein Befehl im Skript eines Headless-Clients
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptStep {
+    /// Über "Join Game" beitreten (nur aus dem Menü)
+    Connect(String),
+    /// Warten, bis der Client spielbereit ist; sonst Abbruch mit Fehler
+    WaitConnected(Duration),
+    Wait(Duration),
+    Say(String),
+    /// Session verlassen, ohne automatisch wiederzuverbinden
+    Disconnect,
+    Exit,
+}
+
+/// Fehler beim Einlesen eines Skripts, mit Zeilennummer ab 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// Liest ein Skript: ein Befehl pro Zeile, `#` leitet Kommentare ein.
+///
+/// ```text
+/// connect 127.0.0.1:8080
+/// wait-connected 30
+/// say Hallo!
+/// wait 5
+/// disconnect
+/// exit
+/// ```
+pub fn parse_script(text: &str) -> Result<Vec<ScriptStep>, ScriptError> {
+    let mut steps = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| ScriptError {
+            line: index + 1,
+            message,
+        };
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        let argument = argument.trim();
+        let seconds = |argument: &str| parse_seconds(argument).map_err(error);
+        let step = match command {
+            "connect" if !argument.is_empty() => ScriptStep::Connect(argument.to_string()),
+            "wait-connected" if argument.is_empty() => ScriptStep::WaitConnected(CONNECT_WAIT),
+            "wait-connected" => ScriptStep::WaitConnected(seconds(argument)?),
+            "wait" => ScriptStep::Wait(seconds(argument)?),
+            "say" if !argument.is_empty() => ScriptStep::Say(argument.to_string()),
+            "disconnect" => ScriptStep::Disconnect,
+            "exit" => ScriptStep::Exit,
+            "connect" | "say" => return Err(error(format!("'{command}' needs an argument"))),
+            command => return Err(error(format!("unknown command '{command}'"))),
+        };
+        steps.push(step);
+    }
+    Ok(steps)
+}
+
+/// Endliche, nicht negative Sekundenzahl; auch Value-Parser für `--stay`
+pub fn parse_seconds(argument: &str) -> Result<Duration, String> {
+    argument
+        .parse::<f32>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f32)
+        .ok_or_else(|| format!("'{argument}' is not a number of seconds"))
+}
+
+/// Skript aus `--say` und `--stay`: beitreten, Nachrichten senden, warten, beenden
+pub fn default_script(args: &LaunchArgs) -> Vec<ScriptStep> {
+    let mut steps = vec![ScriptStep::WaitConnected(CONNECT_WAIT)];
+    steps.extend(args.say.iter().cloned().map(ScriptStep::Say));
+    steps.extend(args.stay.map(ScriptStep::Wait));
+    steps.push(ScriptStep::Exit);
+    steps
+}
+
+/// Plugin, das ein Skript Schritt für Schritt abarbeitet. Endet es (oder schlägt ein
+/// Schritt fehl), gibt es den [`ScriptStats::report`] auf stdout aus und beendet die App.
+pub struct HeadlessPlugin {
+    pub script: Vec<ScriptStep>,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HeadlessScript {
+            steps: self.script.iter().cloned().collect(),
+            started: None,
+            finished: false,
+        })
+        .init_resource::<SkipUnverifiedPrompt>()
+        .insert_resource(ScriptStats {
+            // Mit --connect beginnt der Beitritt schon beim Start
+            connect_started: Some(Instant::now()),
+            ..default()
+        })
+        .add_systems(Update, (track_chat_round_trips, run_script).chain());
+    }
+}
+
+/// Verbleibende Schritte; `started` gehört zum vordersten
+#[derive(Resource, Debug)]
+pub struct HeadlessScript {
+    pub steps: VecDeque<ScriptStep>,
+    started: Option<Instant>,
+    finished: bool,
+}
+
+/// Messwerte eines Skriptlaufs; werden beim Beenden als Report ausgegeben
+#[derive(Resource, Debug, Default)]
+pub struct ScriptStats {
+    /// Dauer vom Beitritt bis zur Spielbereitschaft
+    pub connect_times: Vec<Duration>,
+    /// Dauer vom Senden einer Chatnachricht bis zu ihrem Echo vom Server
+    pub chat_round_trips: Vec<Duration>,
+    pub messages_sent: u32,
+    pub errors: Vec<String>,
+    connect_started: Option<Instant>,
+    pending_chat: Vec<(String, Instant)>,
+}
+
+impl ScriptStats {
+    /// Report für Auswertungen wie `cargo xtask swarm`: eine Zeile mit den Messwerten,
+    /// danach eine Zeile je Fehler
+    pub fn report(&self) -> String {
+        let millis = |times: &[Duration]| {
+            times
+                .iter()
+                .map(|time| time.as_millis().to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut report = format!(
+            "headless-report connect_ms={} chat_ms={} sent={} lost_chat={} errors={}",
+            millis(&self.connect_times),
+            millis(&self.chat_round_trips),
+            self.messages_sent,
+            self.pending_chat.len(),
+            self.errors.len()
+        );
+        for error in &self.errors {
+            report.push_str("\nheadless-error ");
+            report.push_str(error);
+        }
+        report
+    }
+}
+
+/// Headless-App: `MinimalPlugins` und die Client-Plugins, ohne Fenster, Rendering
+/// und egui. Verbindet sich wie bei `--connect` über die Menü-Events von chicken.
+pub fn headless_app(args: LaunchArgs, script: Vec<ScriptStep>) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(HEADLESS_FRAME)),
+        LogPlugin::default(),
+        StatesPlugin,
+        // Liefert die Tastatur-Resources, die InputActionsPlugin auswertet
+        InputPlugin,
+        LaunchPlugin { args },
+        FOSClientPlugin,
+        HeadlessPlugin { script },
+    ));
+    app
+}
+
+/// Einstieg für `--headless`: lädt das Skript und lässt die App laufen
+pub fn run(args: LaunchArgs) -> AppExit {
+    let script = match &args.script {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => parse_script(&text),
+            Err(err) => {
+                eprintln!("Could not read script {}: {err}", path.display());
+                return AppExit::error();
+            }
+        },
+        None => Ok(default_script(&args)),
+    };
+    match script {
+        Ok(script) => headless_app(args, script).run(),
+        Err(err) => {
+            eprintln!("Invalid script: {err}");
+            AppExit::error()
+        }
+    }
+}
+
+/// Misst die Zeit bis zum Echo der eigenen Nachrichten
+fn track_chat_round_trips(
+    mut messages: MessageReader<ServerChat>,
+    mut stats: ResMut<ScriptStats>,
+    chat: Res<ChatState>,
+) {
+    for message in messages.read() {
+        if message.sender_name != chat.own_player_name {
+            continue;
+        }
+        if let Some(index) = stats
+            .pending_chat
+            .iter()
+            .position(|(text, _)| *text == message.text)
+        {
+            let (_, sent) = stats.pending_chat.remove(index);
+            stats.chat_round_trips.push(sent.elapsed());
+        }
+    }
+}
+
+fn run_script(
+    mut commands: Commands,
+    mut script: ResMut<HeadlessScript>,
+    mut stats: ResMut<ScriptStats>,
+    mut chat: ResMut<ChatState>,
+    scope: Res<State<AppScope>>,
+    status: Option<Res<State<ClientConnectionStatus>>>,
+    reconnecting: Option<Res<Reconnecting>>,
+) {
+    if script.finished {
+        return;
+    }
+    if reconnecting.is_some_and(|reconnecting| reconnecting.is_added()) {
+        stats.errors.push("connection lost".to_string());
+        stats.connect_started = Some(Instant::now());
+    }
+    let Some(step) = script.steps.front().cloned() else {
+        finish(&mut commands, &mut script, &stats, AppExit::Success);
+        return;
+    };
+    let first_frame = script.started.is_none();
+    let started = *script.started.get_or_insert_with(Instant::now);
+    let playing = status.is_some_and(|status| *status.get() == ClientConnectionStatus::Playing)
+        && chat.history_loaded;
+
+    let result = match &step {
+        ScriptStep::Connect(_) if *scope.get() != AppScope::Menu => {
+            Err("connect needs the client to be in the menu".to_string())
+        }
+        ScriptStep::Connect(address) => {
+            commands.trigger(RunLaunchAction(LaunchAction::Connect {
+                address: address.clone(),
+            }));
+            stats.connect_started = Some(Instant::now());
+            Ok(true)
+        }
+        ScriptStep::WaitConnected(_) if playing => {
+            if let Some(connect_started) = stats.connect_started.take() {
+                stats.connect_times.push(connect_started.elapsed());
+            }
+            Ok(true)
+        }
+        ScriptStep::WaitConnected(timeout) if started.elapsed() > *timeout => {
+            Err(format!("not connected after {:.0}s", timeout.as_secs_f32()))
+        }
+        ScriptStep::WaitConnected(_) => Ok(false),
+        ScriptStep::Wait(duration) => Ok(started.elapsed() >= *duration),
+        ScriptStep::Say(text) if !playing => {
+            Err(format!("cannot say '{text}' without a connection"))
+        }
+        ScriptStep::Say(text) => {
+            chat.outbox.push(text.clone());
+            stats.messages_sent += 1;
+            stats.pending_chat.push((text.clone(), Instant::now()));
+            Ok(true)
+        }
+        ScriptStep::Disconnect => {
+            if first_frame && *scope.get() == AppScope::Session {
+                commands.trigger(SetPauseMenu::Exit);
+            }
+            Ok(*scope.get() == AppScope::Menu)
+        }
+        ScriptStep::Exit => {
+            finish(&mut commands, &mut script, &stats, AppExit::Success);
+            return;
+        }
+    };
+    match result {
+        Ok(true) => {
+            info!("Script: {step:?} done");
+            script.steps.pop_front();
+            script.started = None;
+        }
+        Ok(false) => {}
+        Err(err) => {
+            error!("Script: {err}");
+            stats.errors.push(err);
+            finish(&mut commands, &mut script, &stats, AppExit::error());
+        }
+    }
+}
+
+/// Gibt den Report aus und beendet die App
+fn finish(
+    commands: &mut Commands,
+    script: &mut HeadlessScript,
+    stats: &ScriptStats,
+    exit: AppExit,
+) {
+    script.finished = true;
+    println!("{}", stats.report());
+    commands.write_message(exit);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_are_parsed_line_by_line() {
+        let script = "\
+            # Soak test\n\
+            connect 127.0.0.1:8080\n\
+            wait-connected\n\
+            say Hallo zusammen!\n\
+            wait 2.5\n\
+            disconnect\n\
+            exit\n";
+        assert_eq!(
+            parse_script(script),
+            Ok(vec![
+                ScriptStep::Connect("127.0.0.1:8080".to_string()),
+                ScriptStep::WaitConnected(CONNECT_WAIT),
+                ScriptStep::Say("Hallo zusammen!".to_string()),
+                ScriptStep::Wait(Duration::from_millis(2500)),
+                ScriptStep::Disconnect,
+                ScriptStep::Exit,
+            ])
+        );
+
+        let error = |line, message: &str| {
+            Err(ScriptError {
+                line,
+                message: message.to_string(),
+            })
+        };
+        assert_eq!(
+            parse_script("exit\n\njump"),
+            error(3, "unknown command 'jump'")
+        );
+        assert_eq!(
+            parse_script("wait soon"),
+            error(1, "'soon' is not a number of seconds")
+        );
+        assert_eq!(parse_script("say"), error(1, "'say' needs an argument"));
+    }
+
+    #[test]
+    fn report_lists_timings_and_errors() {
+        let stats = ScriptStats {
+            connect_times: vec![Duration::from_millis(250), Duration::from_millis(1200)],
+            chat_round_trips: vec![Duration::from_millis(18)],
+            messages_sent: 2,
+            errors: vec!["connection lost".to_string()],
+            pending_chat: vec![("Hallo".to_string(), Instant::now())],
+            ..default()
+        };
+        assert_eq!(
+            stats.report(),
+            "headless-report connect_ms=250,1200 chat_ms=18 sent=2 lost_chat=1 errors=1\n\
+             headless-error connection lost"
+        );
+        assert_eq!(
+            ScriptStats::default().report(),
+            "headless-report connect_ms= chat_ms= sent=0 lost_chat=0 errors=0"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use {
+    crate::paths,
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::{
+            menu::multiplayer::{SetNewHostGame, SetSavedHostGame},
+            session::SetGoingPublicStep,
+        },
+        states::{app::AppScope, session::ServerStatus},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt, fs,
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Erlaubte Länge des Servernamens in Zeichen
+pub const SERVER_NAME_LENGTH: RangeInclusive<usize> = 3..=32;
+pub const MOTD_MAX_LENGTH: usize = 120;
+
+/// Port des gehosteten Servers. chicken nimmt beim Hosten keinen Port entgegen;
+/// das ist dessen Standard, an dem sich der Info-Dienst ausrichtet.
+pub const HOST_PORT: u16 = 8080;
+
+/// Plugin für "Configure Server" beim Hosten.
+///
+/// Beim `Confirm` beider Host-Abläufe wird die [`HostConfig`]-Resource gesetzt;
+/// ist "LAN" gewählt, wird der Server nach dem Start direkt geöffnet. Name und MOTD
+/// liefert der Info-Dienst an andere Clients aus. Port und Spielerlimit lassen sich
+/// nicht an chicken übergeben und werden daher weder abgefragt noch angezeigt;
+/// ebenso kein Passwort, da chicken es beim Verbinden nicht prüfen könnte.
+pub struct HostConfigPlugin;
+
+impl Plugin for HostConfigPlugin {
+    fn build(&self, app: &mut App) {
+        let form = HostConfigForm::from(HostConfig::load_or_default(&host_config_path()));
+        app.insert_resource(form)
+            .add_observer(on_host_new_confirm)
+            .add_observer(on_host_saved_confirm)
+            .add_systems(OnEnter(ServerStatus::Running), apply_initial_visibility)
+            .add_systems(OnExit(AppScope::Session), clear_host_config);
+    }
+}
+
+/// Pfad der zuletzt verwendeten Server-Konfiguration
+fn host_config_path() -> PathBuf {
+    paths::config_dir().join("host.toml")
+}
+
+/// Sichtbarkeit des Servers direkt nach dem Start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostVisibility {
+    /// Nur lokal, kann im Pausenmenü geöffnet werden
+    #[default]
+    Private,
+    /// Sofort im LAN sichtbar
+    Lan,
+}
+
+impl HostVisibility {
+    pub const ALL: [HostVisibility; 2] = [HostVisibility::Private, HostVisibility::Lan];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HostVisibility::Private => "Private",
+            HostVisibility::Lan => "LAN",
+        }
+    }
+}
+
+/// Konfiguration des gehosteten Servers der aktuellen Session
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HostConfig {
+    pub server_name: String,
+    pub motd: String,
+    pub visibility: HostVisibility,
+}
+
+impl Default for HostConfig {
+    fn default() -> Self {
+        Self {
+            server_name: "My Server".to_string(),
+            motd: String::new(),
+            visibility: HostVisibility::default(),
+        }
+    }
+}
+
+/// Grund, warum eine Server-Konfiguration nicht übernommen werden kann
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostConfigError {
+    NameLength,
+    MotdTooLong,
+}
+
+impl fmt::Display for HostConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostConfigError::NameLength => write!(
+                f,
+                "Server name must be {} to {} characters long",
+                SERVER_NAME_LENGTH.start(),
+                SERVER_NAME_LENGTH.end()
+            ),
+            HostConfigError::MotdTooLong => {
+                write!(
+                    f,
+                    "Message of the day must be at most {MOTD_MAX_LENGTH} characters"
+                )
+            }
+        }
+    }
+}
+
+impl HostConfig {
+    /// Prüft alle Felder
+    pub fn validate(&self) -> Vec<HostConfigError> {
+        let mut errors = Vec::new();
+        if !SERVER_NAME_LENGTH.contains(&self.server_name.trim().chars().count()) {
+            errors.push(HostConfigError::NameLength);
+        }
+        if self.motd.chars().count() > MOTD_MAX_LENGTH {
+            errors.push(HostConfigError::MotdTooLong);
+        }
+        errors
+    }
+
+    pub fn load_or_default(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).unwrap_or_else(|err| {
+                warn!("Invalid host config {}: {err}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("serializing host config")?;
+        fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// Formularzustand von "Configure Server"; gemeinsam für neuen und gespeicherten Spielstand
+#[derive(Resource, Debug)]
+pub struct HostConfigForm {
+    pub config: HostConfig,
+}
+
+impl From<HostConfig> for HostConfigForm {
+    fn from(config: HostConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl HostConfigForm {
+    /// Konfiguration mit getrimmten Texten
+    pub fn to_config(&self) -> HostConfig {
+        HostConfig {
+            server_name: self.config.server_name.trim().to_string(),
+            motd: self.config.motd.trim().to_string(),
+            ..self.config.clone()
+        }
+    }
+}
+
+fn on_host_new_confirm(event: On<SetNewHostGame>, commands: Commands, form: Res<HostConfigForm>) {
+    if matches!(*event, SetNewHostGame::Confirm) {
+        hand_over_host_config(commands, &form);
+    }
+}
+
+fn on_host_saved_confirm(
+    event: On<SetSavedHostGame>,
+    commands: Commands,
+    form: Res<HostConfigForm>,
+) {
+    if matches!(*event, SetSavedHostGame::Confirm) {
+        hand_over_host_config(commands, &form);
+    }
+}
+
+fn hand_over_host_config(mut commands: Commands, form: &HostConfigForm) {
+    let config = form.to_config();
+    if !config.validate().is_empty() {
+        warn!("Host config is invalid, server keeps its defaults");
+        return;
+    }
+    if let Err(err) = config.save(&host_config_path()) {
+        warn!("Could not save host config: {err:#}");
+    }
+    info!("Hosting \"{}\"", config.server_name);
+    commands.insert_resource(config);
+}
+
+fn apply_initial_visibility(mut commands: Commands, config: Option<Res<HostConfig>>) {
+    if config.is_some_and(|config| config.visibility == HostVisibility::Lan) {
+        commands.trigger(SetGoingPublicStep::Start);
+    }
+}
+
+fn clear_host_config(mut commands: Commands) {
+    commands.remove_resource::<HostConfig>();
+}
+
+// --- UI ---
+
+/// Rendert das Formular. Gibt `true` zurück, wenn die Konfiguration gültig ist.
+pub fn render_host_config_form(ui: &mut egui::Ui, form: &mut HostConfigForm) -> bool {
+    egui::Grid::new("host_config_form")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Server name");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.config.server_name)
+                    .char_limit(*SERVER_NAME_LENGTH.end()),
+            );
+            ui.end_row();
+
+            ui.label("Message of the day");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.config.motd)
+                    .char_limit(MOTD_MAX_LENGTH)
+                    .hint_text("Welcome!"),
+            );
+            ui.end_row();
+
+            ui.label("Visibility");
+            ui.horizontal(|ui| {
+                for visibility in HostVisibility::ALL {
+                    ui.radio_value(&mut form.config.visibility, visibility, visibility.label());
+                }
+            });
+            ui.end_row();
+        });
+
+    let errors = form.to_config().validate();
+    for error in &errors {
+        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error.to_string());
+    }
+    errors.is_empty()
+}
+
+/// Zusammenfassung für das Pausenmenü
+pub fn render_host_summary(ui: &mut egui::Ui, config: &HostConfig) {
+    ui.label(format!("Server: {}", config.server_name));
+    if !config.motd.is_empty() {
+        ui.label(format!("MOTD: {}", config.motd));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_config_is_validated() {
+        assert!(HostConfig::default().validate().is_empty());
+
+        let config = HostConfig {
+            server_name: " x ".to_string(),
+            motd: "a".repeat(MOTD_MAX_LENGTH + 1),
+            ..default()
+        };
+        assert_eq!(
+            config.validate(),
+            vec![HostConfigError::NameLength, HostConfigError::MotdTooLong]
+        );
+    }
+}
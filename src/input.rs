@@ -0,0 +1,479 @@
+use {
+    crate::settings::UserSettings,
+    bevy::{input::InputSystems, platform::collections::HashSet, prelude::*},
+    bevy_egui::egui,
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// Plugin für die Action-Schicht zwischen rohen Eingaben und Spiel-Logik.
+///
+/// Systeme fragen [`ActionState`] statt `ButtonInput<KeyCode>` ab; die Belegung
+/// kommt aus `UserSettings::controls` und ist über die Controls-Seite änderbar.
+pub struct InputActionsPlugin;
+
+impl Plugin for InputActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>()
+            .init_resource::<BindingCapture>()
+            .add_systems(
+                PreUpdate,
+                // Erst auswerten, dann aufnehmen: die frisch belegte Taste löst
+                // so nicht im selben Frame ihre neue Aktion aus
+                (update_action_state, capture_binding)
+                    .chain()
+                    .in_set(InputActionSystems)
+                    .after(InputSystems),
+            );
+    }
+}
+
+/// Set, in dem [`ActionState`] aktualisiert wird
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputActionSystems;
+
+/// Alle belegbaren Aktionen des Clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputAction {
+    OpenChat,
+    SendChat,
+    CloseChat,
+    AutocompleteNext,
+    AutocompletePrevious,
+    AutocompleteAccept,
+    PauseMenu,
+    ToggleDebugOverlay,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    MenuConfirm,
+    MenuBack,
+}
+
+/// In welchem Kontext eine Aktion aktiv ist. Gleiche Belegungen sind nur
+/// innerhalb eines Kontexts (oder mit `Global`) ein Konflikt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputContext {
+    /// Spiel läuft, Chat geschlossen
+    Gameplay,
+    /// Chat ist geöffnet
+    Chat,
+    /// Hauptmenü oder Pausenmenü
+    Menu,
+    /// Immer aktiv
+    Global,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 14] = [
+        InputAction::OpenChat,
+        InputAction::SendChat,
+        InputAction::CloseChat,
+        InputAction::AutocompleteNext,
+        InputAction::AutocompletePrevious,
+        InputAction::AutocompleteAccept,
+        InputAction::PauseMenu,
+        InputAction::ToggleDebugOverlay,
+        InputAction::MenuUp,
+        InputAction::MenuDown,
+        InputAction::MenuLeft,
+        InputAction::MenuRight,
+        InputAction::MenuConfirm,
+        InputAction::MenuBack,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::OpenChat => "Open chat",
+            InputAction::SendChat => "Send chat message",
+            InputAction::CloseChat => "Close chat",
+            InputAction::AutocompleteNext => "Autocomplete: next",
+            InputAction::AutocompletePrevious => "Autocomplete: previous",
+            InputAction::AutocompleteAccept => "Autocomplete: accept",
+            InputAction::PauseMenu => "Pause menu",
+            InputAction::ToggleDebugOverlay => "Toggle debug overlay",
+            InputAction::MenuUp => "Menu: up",
+            InputAction::MenuDown => "Menu: down",
+            InputAction::MenuLeft => "Menu: left",
+            InputAction::MenuRight => "Menu: right",
+            InputAction::MenuConfirm => "Menu: confirm",
+            InputAction::MenuBack => "Menu: back",
+        }
+    }
+
+    pub fn context(self) -> InputContext {
+        match self {
+            InputAction::OpenChat | InputAction::PauseMenu => InputContext::Gameplay,
+            InputAction::SendChat
+            | InputAction::CloseChat
+            | InputAction::AutocompleteNext
+            | InputAction::AutocompletePrevious
+            | InputAction::AutocompleteAccept => InputContext::Chat,
+            InputAction::ToggleDebugOverlay => InputContext::Global,
+            InputAction::MenuUp
+            | InputAction::MenuDown
+            | InputAction::MenuLeft
+            | InputAction::MenuRight
+            | InputAction::MenuConfirm
+            | InputAction::MenuBack => InputContext::Menu,
+        }
+    }
+
+    /// Ob die Aktion auf der Controls-Seite neu belegt werden kann. Das Pausenmenü
+    /// öffnet chicken fest mit Escape; damit Öffnen und Schließen dieselbe Taste
+    /// bleiben, ist auch das Schließen nicht belegbar.
+    pub fn rebindable(self) -> bool {
+        self != InputAction::PauseMenu
+    }
+
+    fn default_bindings(self) -> Vec<InputBinding> {
+        use InputBinding::{Gamepad, Key};
+        match self {
+            InputAction::OpenChat => vec![Key(KeyCode::Enter), Key(KeyCode::KeyT)],
+            InputAction::SendChat => vec![Key(KeyCode::Enter), Key(KeyCode::NumpadEnter)],
+            InputAction::CloseChat => vec![Key(KeyCode::Escape), Gamepad(GamepadButton::East)],
+            InputAction::AutocompleteNext => vec![Key(KeyCode::ArrowDown)],
+            InputAction::AutocompletePrevious => vec![Key(KeyCode::ArrowUp)],
+            InputAction::AutocompleteAccept => vec![Key(KeyCode::Tab)],
+            InputAction::PauseMenu => vec![Key(KeyCode::Escape)],
+            InputAction::ToggleDebugOverlay => {
+                vec![Key(KeyCode::F3), Gamepad(GamepadButton::Select)]
+            }
+            InputAction::MenuUp => vec![Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)],
+            InputAction::MenuDown => {
+                vec![Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)]
+            }
+            InputAction::MenuLeft => {
+                vec![Key(KeyCode::ArrowLeft), Gamepad(GamepadButton::DPadLeft)]
+            }
+            InputAction::MenuRight => {
+                vec![Key(KeyCode::ArrowRight), Gamepad(GamepadButton::DPadRight)]
+            }
+            InputAction::MenuConfirm => vec![Key(KeyCode::Enter), Gamepad(GamepadButton::South)],
+            InputAction::MenuBack => vec![Key(KeyCode::Escape), Gamepad(GamepadButton::East)],
+        }
+    }
+
+    fn conflicts_with(self, other: InputAction) -> bool {
+        self != other
+            && (self.context() == other.context()
+                || self.context() == InputContext::Global
+                || other.context() == InputContext::Global)
+    }
+}
+
+/// Eine einzelne Taste bzw. ein Gamepad-Button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputBinding {
+    Key(KeyCode),
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    pub fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            InputBinding::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+}
+
+/// Belegung aller Aktionen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputBindings(BTreeMap<InputAction, Vec<InputBinding>>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self(
+            InputAction::ALL
+                .into_iter()
+                .map(|action| (action, action.default_bindings()))
+                .collect(),
+        )
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: InputAction) -> &[InputBinding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn add(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.0.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn remove(&mut self, action: InputAction, binding: InputBinding) {
+        if let Some(bindings) = self.0.get_mut(&action) {
+            bindings.retain(|existing| *existing != binding);
+        }
+    }
+
+    pub fn reset(&mut self, action: InputAction) {
+        self.0.insert(action, action.default_bindings());
+    }
+
+    /// Ergänzt Aktionen, die in einer älteren Einstellungsdatei noch fehlen, und setzt
+    /// nicht belegbare Aktionen auf ihre Standardbelegung zurück
+    pub fn fill_missing(&mut self) {
+        for action in InputAction::ALL {
+            if !action.rebindable() {
+                self.reset(action);
+            }
+            self.0
+                .entry(action)
+                .or_insert_with(|| action.default_bindings());
+        }
+    }
+
+    /// Aktionen, die sich mit `action` eine Belegung im selben Kontext teilen
+    pub fn conflicts(&self, action: InputAction) -> Vec<(InputAction, InputBinding)> {
+        let own = self.get(action);
+        self.0
+            .iter()
+            .filter(|(other, _)| action.conflicts_with(**other))
+            .flat_map(|(other, bindings)| {
+                bindings
+                    .iter()
+                    .filter(|binding| own.contains(binding))
+                    .map(move |binding| (*other, *binding))
+            })
+            .collect()
+    }
+}
+
+/// Aktuell ausgelöste Aktionen, einmal pro Frame aus den Eingaben berechnet
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+}
+
+fn update_action_state(
+    mut state: ResMut<ActionState>,
+    settings: Res<UserSettings>,
+    capture: Res<BindingCapture>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+) {
+    state.pressed.clear();
+    state.just_pressed.clear();
+
+    // Während eine Taste neu belegt wird, löst nichts anderes aus
+    if capture.0.is_some() {
+        return;
+    }
+
+    for action in InputAction::ALL {
+        for binding in settings.controls.bindings.get(action) {
+            let (pressed, just_pressed) = match binding {
+                InputBinding::Key(key) => keys.as_ref().map_or((false, false), |keys| {
+                    (keys.pressed(*key), keys.just_pressed(*key))
+                }),
+                InputBinding::Gamepad(button) => (
+                    gamepads.iter().any(|gamepad| gamepad.pressed(*button)),
+                    gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)),
+                ),
+            };
+            if pressed {
+                state.pressed.insert(action);
+            }
+            if just_pressed {
+                state.just_pressed.insert(action);
+            }
+        }
+    }
+}
+
+/// Aktion, für die gerade eine neue Belegung aufgenommen wird
+#[derive(Resource, Debug, Default)]
+pub struct BindingCapture(pub Option<InputAction>);
+
+fn capture_binding(
+    mut capture: ResMut<BindingCapture>,
+    mut settings: ResMut<UserSettings>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let Some(action) = capture.0 else {
+        return;
+    };
+
+    let key = keys
+        .as_ref()
+        .and_then(|keys| keys.get_just_pressed().next().copied());
+    if key == Some(KeyCode::Escape) {
+        capture.0 = None;
+        return;
+    }
+
+    let binding = key.map(InputBinding::Key).or_else(|| {
+        gamepads
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+            .map(InputBinding::Gamepad)
+    });
+
+    if let Some(binding) = binding {
+        settings.controls.bindings.add(action, binding);
+        capture.0 = None;
+    }
+}
+
+// --- UI ---
+
+/// Rendert die Controls-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_controls_settings(
+    ui: &mut egui::Ui,
+    bindings: &mut InputBindings,
+    capture: &mut BindingCapture,
+) -> bool {
+    let mut changed = false;
+
+    egui::Grid::new("controls_bindings")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for action in InputAction::ALL
+                .into_iter()
+                .filter(|action| action.rebindable())
+            {
+                ui.label(action.label());
+
+                ui.horizontal_wrapped(|ui| {
+                    for binding in bindings.get(action).to_vec() {
+                        if ui
+                            .button(format!("{} ✖", binding.label()))
+                            .on_hover_text("Remove binding")
+                            .clicked()
+                        {
+                            bindings.remove(action, binding);
+                            changed = true;
+                        }
+                    }
+                    if capture.0 == Some(action) {
+                        ui.label("Press a key or button… (Esc to cancel)");
+                    } else if ui.button("+").on_hover_text("Add binding").clicked() {
+                        capture.0 = Some(action);
+                    }
+                    if ui.small_button("↺").on_hover_text("Reset").clicked() {
+                        bindings.reset(action);
+                        changed = true;
+                    }
+                });
+
+                let conflicts = bindings.conflicts(action);
+                if conflicts.is_empty() {
+                    ui.label("");
+                } else {
+                    let text = conflicts
+                        .iter()
+                        .map(|(other, binding)| {
+                            format!("{} also on {}", binding.label(), other.label())
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), text);
+                }
+                ui.end_row();
+            }
+        });
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
+        let bindings = InputBindings::default();
+        for action in InputAction::ALL {
+            assert!(bindings.conflicts(action).is_empty(), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn same_key_in_same_context_conflicts() {
+        let mut bindings = InputBindings::default();
+        bindings.add(
+            InputAction::AutocompleteAccept,
+            InputBinding::Key(KeyCode::Enter),
+        );
+
+        assert_eq!(
+            bindings.conflicts(InputAction::AutocompleteAccept),
+            vec![(InputAction::SendChat, InputBinding::Key(KeyCode::Enter))]
+        );
+    }
+
+    #[test]
+    fn global_actions_conflict_with_every_context() {
+        let mut bindings = InputBindings::default();
+        bindings.add(
+            InputAction::ToggleDebugOverlay,
+            InputBinding::Key(KeyCode::KeyT),
+        );
+
+        assert_eq!(
+            bindings.conflicts(InputAction::ToggleDebugOverlay),
+            vec![(InputAction::OpenChat, InputBinding::Key(KeyCode::KeyT))]
+        );
+    }
+
+    #[test]
+    fn bindings_roundtrip_through_toml() {
+        #[derive(Serialize, Deserialize)]
+        struct Wrapper {
+            bindings: InputBindings,
+        }
+
+        let content = toml::to_string(&Wrapper {
+            bindings: InputBindings::default(),
+        })
+        .unwrap();
+        let parsed: Wrapper = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.bindings, InputBindings::default());
+    }
+
+    #[test]
+    fn fixed_actions_are_reset_on_load() {
+        let mut bindings = InputBindings::default();
+        bindings.add(InputAction::PauseMenu, InputBinding::Key(KeyCode::KeyP));
+        bindings.add(InputAction::OpenChat, InputBinding::Key(KeyCode::KeyY));
+        bindings.fill_missing();
+
+        assert_eq!(
+            bindings.get(InputAction::PauseMenu),
+            [InputBinding::Key(KeyCode::Escape)]
+        );
+        assert!(
+            bindings
+                .get(InputAction::OpenChat)
+                .contains(&InputBinding::Key(KeyCode::KeyY))
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use {
+    crate::{
+        compatibility::RequestJoin,
+        deep_link::{DeepLink, LinkNotices, OpenLink},
+        headless, paths,
+        player_profile::{PlayerNameOverride, PlayerProfileForm},
+        reconnect::Reconnecting,
+        save_browser::SaveBrowser,
+        saves::{self, SaveConfigForm},
+        settings::{Language, LanguageOverride, SettingsPath},
+    },
+    bevy::{ecs::system::SystemParam, prelude::*},
+    chicken::states::{
+        events::menu::{
+            multiplayer::{SetMultiplayerMenu, SetNewHostGame, SetSavedHostGame},
+            singleplayer::{SetSingleplayerMenu, SetSingleplayerNewGame, SetSingleplayerSavedGame},
+        },
+        states::{
+            app::AppScope,
+            menu::{
+                main::MainMenuScreen,
+                multiplayer::{
+                    HostNewGameMenuScreen, HostSavedGameMenuScreen, MultiplayerMenuScreen,
+                },
+                singleplayer::{NewGameMenuScreen, SingleplayerMenuScreen},
+            },
+        },
+    },
+    clap::{ArgGroup, Parser},
+    std::{
+        path::PathBuf,
+        time::{Duration, Instant},
+    },
+};
+
+/// Ohne Fortschritt durch die Menüs wird der Start nach dieser Zeit abgebrochen
+pub const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kommandozeile des Clients
+#[derive(Parser, Debug, Clone, Default, PartialEq)]
+#[command(
+    version,
+    about = "Start the client, optionally skipping the menus",
+    group(ArgGroup::new("start").args(["host", "singleplayer"]))
+)]
+pub struct LaunchArgs {
+    /// Invite link to join, e.g. fos://join/fos.lan:8080
+    #[arg(value_name = "URI", value_parser = DeepLink::parse, conflicts_with_all = ["connect", "host", "singleplayer"])]
+    pub link: Option<DeepLink>,
+    /// Join a server right away (host, host:port or [ipv6]:port)
+    #[arg(long, value_name = "ADDR", conflicts_with_all = ["host", "singleplayer"])]
+    pub connect: Option<String>,
+    /// Host a multiplayer game
+    #[arg(long, conflicts_with = "singleplayer")]
+    pub host: bool,
+    /// Start a singleplayer game
+    #[arg(long, requires = "save")]
+    pub singleplayer: bool,
+    /// Save to load; created with default settings if it does not exist
+    #[arg(long, value_name = "NAME", requires = "start")]
+    pub save: Option<String>,
+    /// Player name for this run
+    #[arg(long, value_name = "PLAYER")]
+    pub name: Option<String>,
+    /// Language of translated texts for this run (en, de)
+    #[arg(long, value_name = "CODE", value_parser = Language::from_code)]
+    pub lang: Option<Language>,
+    /// Settings file to use instead of the one in the config directory
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Run without window or UI, e.g. for bots and CI
+    #[arg(long)]
+    pub headless: bool,
+    /// Script to run in headless mode, one command per line
+    #[arg(long, value_name = "PATH", requires = "headless")]
+    pub script: Option<PathBuf>,
+    /// Chat message to send once connected (headless, repeatable)
+    #[arg(
+        long,
+        value_name = "TEXT",
+        requires = "headless",
+        conflicts_with = "script"
+    )]
+    pub say: Vec<String>,
+    /// Seconds to stay connected before exiting (headless)
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = headless::parse_seconds,
+        requires = "headless",
+        conflicts_with = "script"
+    )]
+    pub stay: Option<Duration>,
+}
+
+/// Was beim Start ohne Umweg über die Menüs gestartet werden soll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchAction {
+    Connect { address: String },
+    Host { save: Option<String> },
+    Singleplayer { save: String },
+}
+
+impl LaunchArgs {
+    pub fn action(&self) -> Option<LaunchAction> {
+        if let Some(link) = &self.link {
+            Some(LaunchAction::Connect {
+                address: link.address.clone(),
+            })
+        } else if let Some(address) = &self.connect {
+            Some(LaunchAction::Connect {
+                address: address.clone(),
+            })
+        } else if self.host {
+            Some(LaunchAction::Host {
+                save: self.save.clone(),
+            })
+        } else if self.singleplayer {
+            self.save
+                .clone()
+                .map(|save| LaunchAction::Singleplayer { save })
+        } else {
+            None
+        }
+    }
+}
+
+/// Plugin für die Startparameter.
+///
+/// Muss vor dem `FOSClientPlugin` hinzugefügt werden, damit `--config` beim Laden
+/// der Einstellungen schon bekannt ist. Die gewählte [`LaunchAction`] wird dann
+/// Schritt für Schritt über die chicken-Events der Menüs ausgeführt, genau wie bei
+/// Klicks im Menü.
+pub struct LaunchPlugin {
+    pub args: LaunchArgs,
+}
+
+impl Plugin for LaunchPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = &self.args.config {
+            app.insert_resource(SettingsPath(path.clone()));
+        }
+        if let Some(language) = self.args.lang {
+            app.insert_resource(LanguageOverride(language));
+        }
+        app.insert_resource(Launch {
+            args: self.args.clone(),
+            plan: None,
+            since: None,
+        })
+        .init_resource::<LinkNotices>()
+        .add_observer(on_run_launch_action)
+        .add_observer(on_open_link)
+        .add_systems(Startup, apply_launch_args)
+        .add_systems(
+            Update,
+            (
+                run_launch_plan.run_if(resource_exists::<Launch>),
+                open_queued_link
+                    .run_if(resource_exists::<QueuedLink>)
+                    .run_if(not(resource_exists::<Reconnecting>)),
+            )
+                .run_if(in_state(AppScope::Menu)),
+        );
+    }
+}
+
+/// Startparameter und der daraus abgeleitete Ablauf
+#[derive(Resource, Debug)]
+struct Launch {
+    args: LaunchArgs,
+    plan: Option<LaunchPlan>,
+    /// Erster Schritt durch die Menüs; ab dann läuft [`LAUNCH_TIMEOUT`]
+    since: Option<Instant>,
+}
+
+/// Welcher Menüpfad durchlaufen wird
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LaunchPlan {
+    Join { address: String },
+    HostNew,
+    HostSaved(String),
+    NewGame,
+    LoadGame(String),
+}
+
+/// Setzt den Namen für diesen Lauf, überträgt den Spielstand ins Formular und legt
+/// den Ablauf fest
+fn apply_launch_args(
+    mut commands: Commands,
+    mut launch: ResMut<Launch>,
+    profile: Res<PlayerProfileForm>,
+    mut save_config: ResMut<SaveConfigForm>,
+    mut notices: ResMut<LinkNotices>,
+) {
+    let args = launch.args.clone();
+    notices
+        .0
+        .extend(args.link.as_ref().and_then(DeepLink::notice));
+    if let Some(name) = &args.name {
+        match PlayerNameOverride::new(name) {
+            Ok(name) => {
+                // Beim direkten Beitritt gibt es kein "Configure Player"
+                commands.insert_resource(name.apply(profile.to_profile()));
+                commands.insert_resource(name);
+            }
+            Err(err) => warn!("Ignoring --name {name}: {err}"),
+        }
+    }
+
+    match args.action() {
+        Some(action) => {
+            let plan = plan_for(action, &mut save_config);
+            info!("Launching {plan:?}");
+            launch.plan = Some(plan);
+        }
+        None => commands.remove_resource::<Launch>(),
+    }
+}
+
+/// Ein vorhandener Spielstand wird geladen, sonst unter diesem Namen angelegt
+fn plan_for(action: LaunchAction, save_config: &mut SaveConfigForm) -> LaunchPlan {
+    let exists = |name: &str| {
+        saves::list_saves(&paths::saves_dir()).iter().any(|slot| {
+            slot.meta.name.eq_ignore_ascii_case(name)
+                || slot
+                    .dir
+                    .file_name()
+                    .is_some_and(|dir| dir.eq_ignore_ascii_case(name))
+        })
+    };
+    match action {
+        LaunchAction::Connect { address } => LaunchPlan::Join { address },
+        LaunchAction::Host { save: None } => LaunchPlan::HostNew,
+        LaunchAction::Host { save: Some(save) } if exists(&save) => LaunchPlan::HostSaved(save),
+        LaunchAction::Singleplayer { save } if exists(&save) => LaunchPlan::LoadGame(save),
+        LaunchAction::Host { save: Some(save) } => {
+            save_config.name = save;
+            LaunchPlan::HostNew
+        }
+        LaunchAction::Singleplayer { save } => {
+            save_config.name = save;
+            LaunchPlan::NewGame
+        }
+    }
+}
+
+/// Startet eine [`LaunchAction`] zur Laufzeit, z.B. aus einem Link oder Skript
+#[derive(Event, Debug, Clone)]
+pub struct RunLaunchAction(pub LaunchAction);
+
+fn on_run_launch_action(
+    event: On<RunLaunchAction>,
+    mut commands: Commands,
+    scope: Res<State<AppScope>>,
+    mut save_config: ResMut<SaveConfigForm>,
+) {
+    let RunLaunchAction(action) = &*event;
+    if *scope.get() != AppScope::Menu {
+        warn!("Ignoring {action:?}: leave the current game first");
+        return;
+    }
+    let plan = plan_for(action.clone(), &mut save_config);
+    info!("Launching {plan:?}");
+    commands.insert_resource(Launch {
+        args: LaunchArgs::default(),
+        plan: Some(plan),
+        since: None,
+    });
+}
+
+/// Link, der während einer Session ankam; wird im Menü geöffnet
+#[derive(Resource, Debug)]
+struct QueuedLink(DeepLink);
+
+/// Ein an diese Instanz weitergereichter Link führt ebenfalls nach "Join Game".
+/// Während einer Session wartet er, bis der Spieler wieder im Menü ist; ein
+/// späterer Link ersetzt einen wartenden.
+fn on_open_link(
+    event: On<OpenLink>,
+    mut commands: Commands,
+    scope: Res<State<AppScope>>,
+    mut notices: ResMut<LinkNotices>,
+) {
+    let OpenLink(link) = &*event;
+    notices.0.extend(link.notice());
+    if *scope.get() != AppScope::Menu {
+        info!("Keeping {link} until the current game ends");
+        notices.0.push(format!(
+            "Invite to {} received. It opens when you leave the current game.",
+            link.address
+        ));
+        commands.insert_resource(QueuedLink(link.clone()));
+        return;
+    }
+    commands.trigger(RunLaunchAction(LaunchAction::Connect {
+        address: link.address.clone(),
+    }));
+}
+
+fn open_queued_link(mut commands: Commands, link: Res<QueuedLink>) {
+    info!("Opening {} after the game ended", link.0);
+    commands.trigger(RunLaunchAction(LaunchAction::Connect {
+        address: link.0.address.clone(),
+    }));
+    commands.remove_resource::<QueuedLink>();
+}
+
+/// Aktuelle Menübildschirme; Unterzustände fehlen, solange ihr Menü nicht offen ist
+#[derive(SystemParam)]
+struct MenuScreens<'w> {
+    main: Res<'w, State<MainMenuScreen>>,
+    singleplayer: Option<Res<'w, State<SingleplayerMenuScreen>>>,
+    new_game: Option<Res<'w, State<NewGameMenuScreen>>>,
+    multiplayer: Option<Res<'w, State<MultiplayerMenuScreen>>>,
+    host_new: Option<Res<'w, State<HostNewGameMenuScreen>>>,
+    host_saved: Option<Res<'w, State<HostSavedGameMenuScreen>>>,
+}
+
+fn current<S: States + Copy>(state: &Option<Res<State<S>>>) -> Option<S> {
+    state.as_ref().map(|state| *state.get())
+}
+
+/// Ein Menüschritt pro Frame; der Zustandswechsel greift im nächsten Frame
+fn run_launch_plan(
+    mut commands: Commands,
+    mut launch: ResMut<Launch>,
+    screens: MenuScreens,
+    mut browser: ResMut<SaveBrowser>,
+) {
+    let launch = &mut *launch;
+    let Some(plan) = &launch.plan else {
+        return;
+    };
+    if launch.since.get_or_insert_with(Instant::now).elapsed() > LAUNCH_TIMEOUT {
+        warn!("Launching {plan:?} got stuck in the menus, giving up");
+        commands.remove_resource::<Launch>();
+        return;
+    }
+
+    let multiplayer = current(&screens.multiplayer);
+    let singleplayer = current(&screens.singleplayer);
+    let mut done = false;
+    match (plan, screens.main.get()) {
+        (
+            LaunchPlan::Join { .. } | LaunchPlan::HostNew | LaunchPlan::HostSaved(_),
+            MainMenuScreen::Overview,
+        ) => {
+            commands.trigger(SetMultiplayerMenu::Overview);
+        }
+        (LaunchPlan::NewGame | LaunchPlan::LoadGame(_), MainMenuScreen::Overview) => {
+            commands.trigger(SetSingleplayerMenu::Overview);
+        }
+        (plan, MainMenuScreen::Multiplayer) => match (plan, multiplayer) {
+            (LaunchPlan::Join { .. }, Some(MultiplayerMenuScreen::Overview)) => {
+                commands.trigger(SetMultiplayerMenu::JoinGame);
+            }
+            (LaunchPlan::Join { address }, Some(MultiplayerMenuScreen::JoinGame)) => {
+                commands.trigger(RequestJoin {
+                    address: address.clone(),
+                });
+                done = true;
+            }
+            (LaunchPlan::HostNew, Some(MultiplayerMenuScreen::Overview)) => {
+                commands.trigger(SetMultiplayerMenu::HostNewGame);
+            }
+            (LaunchPlan::HostNew, Some(MultiplayerMenuScreen::HostNewGame)) => {
+                match current(&screens.host_new) {
+                    Some(HostNewGameMenuScreen::ConfigSave) => {
+                        commands.trigger(SetNewHostGame::Confirm);
+                        done = true;
+                    }
+                    Some(_) => commands.trigger(SetNewHostGame::Next),
+                    None => {}
+                }
+            }
+            (LaunchPlan::HostSaved(_), Some(MultiplayerMenuScreen::Overview)) => {
+                commands.trigger(SetMultiplayerMenu::HostSavedGame);
+            }
+            (LaunchPlan::HostSaved(save), Some(MultiplayerMenuScreen::HostSavedGame)) => {
+                match current(&screens.host_saved) {
+                    Some(HostSavedGameMenuScreen::Overview) => {
+                        if browser.select_by_name(save) {
+                            commands.trigger(SetSavedHostGame::Next);
+                        } else {
+                            warn!("Save {save} not found");
+                            done = true;
+                        }
+                    }
+                    Some(HostSavedGameMenuScreen::ConfigServer) => {
+                        commands.trigger(SetSavedHostGame::Confirm);
+                        done = true;
+                    }
+                    None => {}
+                }
+            }
+            // Untermenü wird gerade gewechselt
+            (_, None) => {}
+            _ => commands.trigger(SetMultiplayerMenu::Overview),
+        },
+        (plan, MainMenuScreen::Singleplayer) => match (plan, singleplayer) {
+            (LaunchPlan::NewGame, Some(SingleplayerMenuScreen::Overview)) => {
+                commands.trigger(SetSingleplayerMenu::NewGame);
+            }
+            (LaunchPlan::NewGame, Some(SingleplayerMenuScreen::NewGame)) => {
+                match current(&screens.new_game) {
+                    Some(NewGameMenuScreen::ConfigSave) => {
+                        commands.trigger(SetSingleplayerNewGame::Confirm);
+                        done = true;
+                    }
+                    Some(_) => commands.trigger(SetSingleplayerNewGame::Next),
+                    None => {}
+                }
+            }
+            (LaunchPlan::LoadGame(_), Some(SingleplayerMenuScreen::Overview)) => {
+                commands.trigger(SetSingleplayerMenu::LoadGame);
+            }
+            (LaunchPlan::LoadGame(save), Some(SingleplayerMenuScreen::LoadGame)) => {
+                if browser.select_by_name(save) {
+                    commands.trigger(SetSingleplayerSavedGame::Confirm);
+                } else {
+                    warn!("Save {save} not found");
+                }
+                done = true;
+            }
+            (_, None) => {}
+            _ => commands.trigger(SetSingleplayerMenu::Overview),
+        },
+        // Anderes Menü offen (z.B. Einstellungen) – dort nicht eingreifen
+        _ => done = true,
+    }
+
+    if done {
+        commands.remove_resource::<Launch>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchArgs, clap::Error> {
+        LaunchArgs::try_parse_from(std::iter::once("client").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn flags_select_the_launch_action() {
+        assert_eq!(parse(&[]).unwrap().action(), None);
+        assert_eq!(
+            parse(&["--connect", "fos.lan:9000"]).unwrap().action(),
+            Some(LaunchAction::Connect {
+                address: "fos.lan:9000".to_string(),
+            })
+        );
+        let host = parse(&["--host", "--name", "Alice"]).unwrap();
+        assert_eq!(host.action(), Some(LaunchAction::Host { save: None }));
+        assert_eq!(host.name.as_deref(), Some("Alice"));
+        assert_eq!(
+            parse(&["--lang", "en-GB"]).unwrap().lang,
+            Some(Language::English)
+        );
+        assert!(parse(&["--lang", "xx"]).is_err());
+        assert_eq!(
+            parse(&["--headless", "--stay", "1.5"]).unwrap().stay,
+            Some(Duration::from_millis(1500))
+        );
+        for stay in ["inf", "NaN", "-1"] {
+            assert!(parse(&["--headless", "--stay", stay]).is_err());
+        }
+        assert_eq!(
+            parse(&["--singleplayer", "--save", "Welt"])
+                .unwrap()
+                .action(),
+            Some(LaunchAction::Singleplayer {
+                save: "Welt".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn invite_link_is_the_first_argument() {
+        let args = parse(&["fos://join/fos.lan:9000"]).unwrap();
+        assert_eq!(
+            args.action(),
+            Some(LaunchAction::Connect {
+                address: "fos.lan:9000".to_string(),
+            })
+        );
+        assert!(parse(&["https://example.com"]).is_err());
+        assert!(parse(&["fos://join/fos.lan", "--host"]).is_err());
+    }
+
+    #[test]
+    fn contradicting_flags_are_rejected() {
+        assert!(parse(&["--connect", "fos.lan", "--host"]).is_err());
+        assert!(parse(&["--host", "--singleplayer", "--save", "Welt"]).is_err());
+        assert!(parse(&["--singleplayer"]).is_err());
+        assert!(parse(&["--save", "Welt"]).is_err());
+    }
+}
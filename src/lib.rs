@@ -1,5 +1,27 @@
+pub mod address;
+pub mod audio;
 pub mod chat;
+pub mod compatibility;
+pub mod connection;
 pub mod debug;
+pub mod deep_link;
+pub mod headless;
+pub mod host_config;
+pub mod input;
+pub mod launch;
+pub mod menu_navigation;
+pub mod paths;
+pub mod player_profile;
+pub mod reconnect;
+pub mod save_browser;
+pub mod save_thumbnails;
+pub mod saves;
+pub mod server_list;
+pub mod server_query;
+pub mod settings;
+pub mod video;
+pub mod wizard;
+pub mod world_config;
 
 // =============================================================================
 // Steam Configuration - Wird von build.rs generiert
@@ -21,13 +43,27 @@ pub use config::IS_RELEASE;
 pub use config::STEAM_APP_ID;
 
 use {
+    address::AddressPlugin,
     bevy::prelude::*,
     chat::ChatPlugin,
     chicken::ChickenPlugin,
     chicken::identity::PlayerIdentity,
     chicken::network::client::LocalIdentity,
     chicken::notifications::{NotificationQueue, notification_lifecycle, on_notify},
+    compatibility::CompatibilityPlugin,
+    connection::ConnectionPlugin,
+    host_config::HostConfigPlugin,
+    input::InputActionsPlugin,
+    player_profile::{PlayerProfile, PlayerProfilePlugin},
+    reconnect::ReconnectPlugin,
+    save_browser::SaveBrowserPlugin,
+    save_thumbnails::SaveThumbnailPlugin,
+    saves::SavesPlugin,
     serde::{Deserialize, Serialize},
+    server_list::ServerListPlugin,
+    server_query::ServerQueryPlugin,
+    settings::SettingsPlugin,
+    world_config::WorldConfigPlugin,
 };
 
 pub struct FOSClientPlugin;
@@ -40,26 +76,44 @@ impl Plugin for FOSClientPlugin {
         app.add_plugins((
             ChickenPlugin,
             // ProtocolPlugin,
+            SettingsPlugin,
+            InputActionsPlugin,
+            PlayerProfilePlugin,
+            WorldConfigPlugin,
+            HostConfigPlugin,
+            SavesPlugin,
+            SaveBrowserPlugin,
+            SaveThumbnailPlugin,
+            ServerListPlugin,
+            AddressPlugin,
+            ServerQueryPlugin,
+            CompatibilityPlugin,
+            (ConnectionPlugin, ReconnectPlugin),
             ChatPlugin,
             // ChickenNotificationPlugin,
         ))
         .init_resource::<NotificationQueue>()
         .add_observer(on_notify)
         .add_systems(Update, notification_lifecycle)
-        .add_systems(PostStartup, init_player_identity);
+        .add_systems(PostStartup, init_player_identity)
+        .add_systems(
+            Update,
+            init_player_identity.run_if(resource_exists_and_changed::<PlayerProfile>),
+        );
     }
 }
 
-/// Initialisiert `PlayerIdentity` aus der lokalen `LocalIdentity` (Ed25519-Key).
-/// Solange kein echter Spielername (Steam, Profil) gesetzt ist, wird Player-{id} verwendet.
+/// Initialisiert `PlayerIdentity` aus dem gewählten `PlayerProfile` bzw. der lokalen
+/// `LocalIdentity` (Ed25519-Key). Ohne Profil (oder Steam) wird Player-{id} verwendet.
 fn init_player_identity(
     mut commands: Commands,
+    profile: Option<Res<PlayerProfile>>,
     local_identity: Option<Res<LocalIdentity>>,
 ) {
-    let Some(local_id) = local_identity else {
-        return;
+    let name = match (profile, local_identity) {
+        (Some(profile), _) => profile.name.clone(),
+        (None, Some(local_id)) => format!("Player-{}", &local_id.player_id[..8]),
+        (None, None) => return,
     };
-    commands.insert_resource(PlayerIdentity::local(
-        format!("Player-{}", &local_id.player_id[..8]),
-    ));
+    commands.insert_resource(PlayerIdentity::local(name));
 }
@@ -1,10 +1,12 @@
 use bevy::app::AppExit;
+use bevy::window::{Monitor, PrimaryMonitor};
 use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
+use clap::Parser;
 
 use chicken::network::client::{
-    ClientTarget, DiscoveredServers, DiscoveryControl, SetClientTarget,
+    ClientTarget, DiscoveredServers, DiscoveryControl,
 };
 use chicken::states::events::app::SetAppScope;
 use chicken::states::events::menu::multiplayer::{
@@ -29,22 +31,68 @@ use chicken::{
     },
     // steam::SteamworksPlugin,
 };
+use client::address::{self, JoinAddress};
+use client::audio::{self, AudioMixerPlugin};
+use client::compatibility::{self, JoinCheck, RequestJoin};
+use client::connection::{self, ConnectionAttempt};
+use client::deep_link::{self, DeepLinkPlugin, LinkNotices};
+use client::host_config::{self, HostConfig, HostConfigForm};
+use client::input::{self, ActionState, BindingCapture, InputAction};
+use client::launch::{LaunchArgs, LaunchPlugin};
+use client::menu_navigation::MenuNavigationPlugin;
+use client::player_profile::{self, PlayerProfileForm};
+use client::reconnect::{self, ReconnectSummary, Reconnecting};
+use client::save_browser::{self, SaveBrowser, SelectedSave};
+use client::saves::{self, SaveConfigForm};
+use client::server_list::{self, ServerListAction, ServerListForm};
+use client::server_query::{self, ServerQueries};
+use client::settings::{
+    self, NetworkSettings, SetSettingsScreen, SettingsMenuScreen, UserSettings,
+};
+use client::video::{self, MonitorInfo, VideoPlugin};
+use client::wizard::{Wizard, WizardAction};
+use client::world_config::{self, WorldConfigForm};
 use client::{FOSClientPlugin, chat, debug::DebugStatePlugin};
 
 fn main() -> AppExit {
     // let steam_client =
     //     SteamworksPlugin::init_app(client::STEAM_APP_ID).expect("failed to initialize steam");
 
+    let args = LaunchArgs::parse();
+    if args.headless {
+        return client::headless::run(args);
+    }
+    // A link opened while the client runs is handed to that instance instead
+    if let Some(link) = &args.link
+        && deep_link::forward_to_running_instance(link)
+    {
+        return AppExit::Success;
+    }
+
     App::new()
         .add_plugins((
             // steam_client,
             DefaultPlugins,
+            // Before FOSClientPlugin so --config is known when the settings load
+            LaunchPlugin { args },
+            DeepLinkPlugin,
             EguiPlugin::default(),
-            WorldInspectorPlugin::new(),
+            WorldInspectorPlugin::new()
+                .run_if(|settings: Res<UserSettings>| settings.interface.show_world_inspector),
             FOSClientPlugin,
             DebugStatePlugin,
+            VideoPlugin,
+            MenuNavigationPlugin,
+            AudioMixerPlugin,
         ))
         .add_systems(Startup, setup_camera_system)
+        .add_systems(Update, handle_menu_back.run_if(in_state(AppScope::Menu)))
+        .add_systems(
+            Update,
+            resume_from_pause_menu
+                .run_if(in_state(AppScope::Session))
+                .run_if(in_state(SessionState::Paused)),
+        )
         .add_systems(
             EguiPrimaryContextPass,
             ui_menu_system.run_if(in_state(AppScope::Menu)),
@@ -71,6 +119,12 @@ fn main() -> AppExit {
             EguiPrimaryContextPass,
             chat::render_chat_ui.run_if(in_state(SessionState::Active)),
         )
+        .add_systems(EguiPrimaryContextPass, video::render_video_revert_dialog)
+        .add_systems(EguiPrimaryContextPass, ui_reconnect_system)
+        .add_systems(
+            EguiPrimaryContextPass,
+            ui_link_notices.run_if(|notices: Res<LinkNotices>| !notices.0.is_empty()),
+        )
         .run()
 }
 
@@ -78,6 +132,60 @@ fn setup_camera_system(mut commands: Commands) {
     commands.spawn(Camera2d);
 }
 
+fn resume_from_pause_menu(mut commands: Commands, actions: Res<ActionState>) {
+    if actions.just_pressed(InputAction::PauseMenu) || actions.just_pressed(InputAction::MenuBack) {
+        commands.trigger(SetPauseMenu::Resume);
+    }
+}
+
+/// Back/Cancel of the current screen, same as its "Back"/"Cancel" button
+fn handle_menu_back(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut egui: EguiContexts,
+    menu_state: Res<State<MainMenuScreen>>,
+    singleplayer_menu_state: Option<Res<State<SingleplayerMenuScreen>>>,
+    multiplayer_menu_state: Option<Res<State<MultiplayerMenuScreen>>>,
+) {
+    if !actions.just_pressed(InputAction::MenuBack) {
+        return;
+    }
+    // Escape first closes an open dialog or ends text editing, not the whole screen
+    if egui.ctx_mut().is_ok_and(|ctx| {
+        ctx.memory(|memory| memory.top_modal_layer().is_some())
+            || ctx
+                .memory(|memory| memory.focused())
+                .is_some_and(|id| egui::text_edit::TextEditState::load(ctx, id).is_some())
+    }) {
+        return;
+    }
+
+    match menu_state.get() {
+        MainMenuScreen::Overview => {}
+        MainMenuScreen::Singleplayer => match singleplayer_menu_state.map(|state| *state.get()) {
+            Some(SingleplayerMenuScreen::Overview) => commands.trigger(SetSingleplayerMenu::Back),
+            Some(SingleplayerMenuScreen::NewGame) => {
+                commands.trigger(SetSingleplayerNewGame::Cancel)
+            }
+            Some(SingleplayerMenuScreen::LoadGame) => {
+                commands.trigger(SetSingleplayerSavedGame::Cancel)
+            }
+            None => {}
+        },
+        MainMenuScreen::Multiplayer => match multiplayer_menu_state.map(|state| *state.get()) {
+            Some(MultiplayerMenuScreen::Overview) => commands.trigger(SetMultiplayerMenu::Back),
+            Some(MultiplayerMenuScreen::HostNewGame) => commands.trigger(SetNewHostGame::Cancel),
+            Some(MultiplayerMenuScreen::HostSavedGame) => {
+                commands.trigger(SetSavedHostGame::Cancel)
+            }
+            Some(MultiplayerMenuScreen::JoinGame) => commands.trigger(SetJoinGame::Cancel),
+            None => {}
+        },
+        MainMenuScreen::Wiki => commands.trigger(SetWikiMenu::Back),
+        MainMenuScreen::Settings => commands.trigger(SetSettingsScreen::Back),
+    }
+}
+
 #[derive(SystemParam)]
 struct MenuUiParams<'w, 's> {
     commands: Commands<'w, 's>,
@@ -92,6 +200,27 @@ struct MenuUiParams<'w, 's> {
     discovered_servers: Option<Res<'w, DiscoveredServers>>,
     discovery_control: Option<ResMut<'w, DiscoveryControl>>,
     client_target: Option<ResMut<'w, ClientTarget>>,
+    settings_menu_state: Option<Res<'w, State<SettingsMenuScreen>>>,
+    user_settings: ResMut<'w, UserSettings>,
+    binding_capture: ResMut<'w, BindingCapture>,
+    forms: MenuForms<'w>,
+    monitors: Query<'w, 's, (&'static Monitor, Has<PrimaryMonitor>)>,
+}
+
+/// Form state of the multi-step new game / host flows
+#[derive(SystemParam)]
+struct MenuForms<'w> {
+    player_profile: ResMut<'w, PlayerProfileForm>,
+    world_config: ResMut<'w, WorldConfigForm>,
+    save_config: ResMut<'w, SaveConfigForm>,
+    host_config: ResMut<'w, HostConfigForm>,
+    save_browser: ResMut<'w, SaveBrowser>,
+    server_list: ResMut<'w, ServerListForm>,
+    server_queries: ResMut<'w, ServerQueries>,
+    join_check: ResMut<'w, JoinCheck>,
+    join_address: ResMut<'w, JoinAddress>,
+    selected_save: Option<Res<'w, SelectedSave>>,
+    connection: Option<ResMut<'w, ConnectionAttempt>>,
 }
 
 struct MenuActions<'w, 's> {
@@ -134,12 +263,25 @@ fn ui_singleplayer_system(
 }
 
 fn ui_client_system(
-    mut _commands: Commands,
+    mut commands: Commands,
     mut egui: EguiContexts,
     app_state: Res<State<AppScope>>,
     game_mode_state: Res<State<SessionType>>,
     client_state: Res<State<ClientConnectionStatus>>,
+    connection: Option<ResMut<ConnectionAttempt>>,
 ) -> Result<(), bevy::prelude::BevyError> {
+    // While joining, a dedicated connecting screen replaces the state readout
+    if let Some(mut attempt) = connection {
+        egui::CentralPanel::default().show(egui.ctx_mut()?, |ui| {
+            ui.vertical_centered(|ui| {
+                if connection::render_connection_screen(ui, &mut attempt) {
+                    commands.trigger(SetPauseMenu::Exit);
+                }
+            });
+        });
+        return Ok(());
+    }
+
     egui::Window::new("APP Game - Client").show(egui.ctx_mut()?, |ui| {
         ui.vertical_centered_justified(|ui| {
             if *app_state.get() == AppScope::Session
@@ -162,6 +304,7 @@ fn ui_game_menu(
     game_mode_state: Res<State<SessionType>>,
     session_state: Res<State<SessionState>>,
     server_visibility: Option<Res<State<ServerVisibility>>>,
+    host_config: Option<Res<HostConfig>>,
 ) -> Result<(), bevy::prelude::BevyError> {
     egui::Window::new("APP Game Menu").show(egui.ctx_mut()?, |ui| {
         ui.vertical_centered_justified(|ui| {
@@ -173,6 +316,9 @@ fn ui_game_menu(
                 });
                 match *game_mode_state.get() {
                     SessionType::Singleplayer => {
+                        if let Some(host_config) = &host_config {
+                            host_config::render_host_summary(ui, host_config);
+                        }
                         if let Some(server_visibility) = server_visibility {
                             match *server_visibility.get() {
                                 ServerVisibility::Private => {
@@ -222,6 +368,11 @@ fn ui_menu_system(mut params: MenuUiParams) -> Result<(), BevyError> {
     let discovered = params.discovered_servers.as_deref();
     let discovery_control = params.discovery_control.as_deref_mut();
     let client_target = params.client_target.as_deref_mut();
+    let settings_screen = params.settings_menu_state.as_deref();
+    let user_settings = &mut params.user_settings;
+    let binding_capture = &mut params.binding_capture;
+    let forms = &mut params.forms;
+    let monitors = MonitorInfo::collect(params.monitors.iter());
 
     // 3. Build mutable "Action" bundle for Commands + Exit
     let mut actions = MenuActions {
@@ -236,7 +387,9 @@ fn ui_menu_system(mut params: MenuUiParams) -> Result<(), BevyError> {
 
             match menu_state.get() {
                 MainMenuScreen::Overview => render_menu_main(ui, &mut actions),
-                MainMenuScreen::Singleplayer => render_singleplayer_menu(ui, &mut actions, single, new_game),
+                MainMenuScreen::Singleplayer => {
+                    render_singleplayer_menu(ui, &mut actions, single, new_game, forms)
+                }
                 MainMenuScreen::Multiplayer => render_multiplayer_menu(
                     ui,
                     &mut actions,
@@ -246,13 +399,56 @@ fn ui_menu_system(mut params: MenuUiParams) -> Result<(), BevyError> {
                     discovered,
                     discovery_control,
                     client_target,
+                    &user_settings.network,
+                    forms,
                 ),
                 MainMenuScreen::Wiki => render_menu_wiki(ui, &mut actions),
-                MainMenuScreen::Settings => render_menu_settings(ui, &mut actions),
+                MainMenuScreen::Settings => render_menu_settings(
+                    ui,
+                    &mut actions,
+                    settings_screen,
+                    user_settings,
+                    binding_capture,
+                    &monitors,
+                ),
             }
         });
     });
 
+    if let Some(attempt) = forms.connection.as_deref_mut() {
+        connection::render_connection_failure(ctx, attempt);
+    }
+
+    Ok(())
+}
+
+// Drawn in menu and session alike: chicken may leave the session when the connection drops
+fn ui_reconnect_system(
+    mut commands: Commands,
+    mut egui: EguiContexts,
+    reconnecting: Option<ResMut<Reconnecting>>,
+    summary: Option<Res<ReconnectSummary>>,
+) -> Result<(), bevy::prelude::BevyError> {
+    if reconnecting.is_none() && summary.is_none() {
+        return Ok(());
+    }
+    let ctx = egui.ctx_mut()?;
+    if let Some(mut reconnecting) = reconnecting {
+        reconnect::render_reconnect_overlay(ctx, &mut reconnecting);
+    }
+    if let Some(summary) = summary
+        && reconnect::render_reconnect_summary(ctx, &summary)
+    {
+        commands.remove_resource::<ReconnectSummary>();
+    }
+    Ok(())
+}
+
+fn ui_link_notices(
+    mut egui: EguiContexts,
+    mut notices: ResMut<LinkNotices>,
+) -> Result<(), bevy::prelude::BevyError> {
+    deep_link::render_link_notices(egui.ctx_mut()?, &mut notices);
     Ok(())
 }
 
@@ -279,6 +475,7 @@ fn render_singleplayer_menu(
     actions: &mut MenuActions,
     state: Option<&State<SingleplayerMenuScreen>>,
     new_game: Option<&State<NewGameMenuScreen>>,
+    forms: &mut MenuForms,
 ) {
     ui.vertical_centered_justified(|ui| {
         let Some(single) = state else {
@@ -290,10 +487,10 @@ fn render_singleplayer_menu(
                 render_singleplayer_overview(ui, actions);
             }
             SingleplayerMenuScreen::NewGame => {
-                render_singleplayer_new_game(ui, actions, new_game);
+                render_singleplayer_new_game(ui, actions, new_game, forms);
             }
             SingleplayerMenuScreen::LoadGame => {
-                render_singleplayer_load_game(ui, actions);
+                render_singleplayer_load_game(ui, actions, forms);
             }
         }
     });
@@ -315,45 +512,69 @@ fn render_singleplayer_new_game(
     ui: &mut egui::Ui,
     actions: &mut MenuActions,
     state: Option<&State<NewGameMenuScreen>>,
+    forms: &mut MenuForms,
 ) {
-    let Some(step) = state else {
+    let Some(step) = state.map(|state| *state.get()) else {
         return;
     };
+    let current = match step {
+        NewGameMenuScreen::ConfigPlayer => 0,
+        NewGameMenuScreen::ConfigWorld => 1,
+        NewGameMenuScreen::ConfigSave => 2,
+    };
 
-    match step.get() {
+    let mut action = Wizard::new(
+        &["Configure Player", "Configure World", "Configure Save"],
+        current,
+    )
+    .finish_label("Start Game")
+    .show(ui, |ui| match step {
         NewGameMenuScreen::ConfigPlayer => {
-            ui.label("Step 1/3: Configure Player");
-            if ui.button("Next →").clicked() {
-                actions.commands.trigger(SetSingleplayerNewGame::Next);
-            }
+            player_profile::render_player_profile_form(ui, &mut forms.player_profile)
         }
         NewGameMenuScreen::ConfigWorld => {
-            ui.label("Step 2/3: Configure World");
-            if ui.button("← Previous").clicked() {
-                actions.commands.trigger(SetSingleplayerNewGame::Previous);
-            }
-            if ui.button("Next →").clicked() {
-                actions.commands.trigger(SetSingleplayerNewGame::Next);
-            }
-        }
-        NewGameMenuScreen::ConfigSave => {
-            ui.label("Step 3/3: Configure Save");
-            if ui.button("← Previous").clicked() {
-                actions.commands.trigger(SetSingleplayerNewGame::Previous);
-            }
-            if ui.button("Start Game").clicked() {
-                actions.commands.trigger(SetSingleplayerNewGame::Confirm);
-            }
+            world_config::render_world_config_form(ui, &mut forms.world_config)
         }
+        NewGameMenuScreen::ConfigSave => saves::render_save_config_form(ui, &mut forms.save_config),
+    });
+    if step == NewGameMenuScreen::ConfigSave {
+        action = confirm_save_overwrite(ui, forms, action);
     }
 
-    if ui.button("Cancel").clicked() {
-        actions.commands.trigger(SetSingleplayerNewGame::Cancel);
+    if let Some(action) = action {
+        actions.commands.trigger(match action {
+            WizardAction::Previous => SetSingleplayerNewGame::Previous,
+            WizardAction::Next => SetSingleplayerNewGame::Next,
+            WizardAction::Finish => SetSingleplayerNewGame::Confirm,
+            WizardAction::Cancel => SetSingleplayerNewGame::Cancel,
+        });
     }
 }
 
-fn render_singleplayer_load_game(ui: &mut egui::Ui, actions: &mut MenuActions) {
-    if ui.button("Load").clicked() {
+/// Holds back "Finish" until overwriting an existing save has been confirmed
+fn confirm_save_overwrite(
+    ui: &mut egui::Ui,
+    forms: &mut MenuForms,
+    action: Option<WizardAction>,
+) -> Option<WizardAction> {
+    let requested = action == Some(WizardAction::Finish);
+    if saves::confirm_start(ui, &mut forms.save_config, requested) {
+        Some(WizardAction::Finish)
+    } else {
+        action.filter(|action| *action != WizardAction::Finish)
+    }
+}
+
+fn render_singleplayer_load_game(
+    ui: &mut egui::Ui,
+    actions: &mut MenuActions,
+    forms: &mut MenuForms,
+) {
+    let selected = save_browser::render_save_browser(ui, &mut forms.save_browser);
+    if ui
+        .add_enabled(selected, egui::Button::new("Load"))
+        .clicked()
+    {
         actions.commands.trigger(SetSingleplayerSavedGame::Confirm);
     }
     if ui.button("Back").clicked() {
@@ -361,6 +582,7 @@ fn render_singleplayer_load_game(ui: &mut egui::Ui, actions: &mut MenuActions) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_multiplayer_menu(
     ui: &mut egui::Ui,
     actions: &mut MenuActions,
@@ -370,6 +592,8 @@ fn render_multiplayer_menu(
     discovered_servers: Option<&DiscoveredServers>,
     discovery_control: Option<&mut DiscoveryControl>,
     client_target: Option<&mut ClientTarget>,
+    network_settings: &NetworkSettings,
+    forms: &mut MenuForms,
 ) {
     ui.vertical_centered_justified(|ui| {
         let Some(multi) = state else {
@@ -381,10 +605,10 @@ fn render_multiplayer_menu(
                 render_multiplayer_overview(ui, actions);
             }
             MultiplayerMenuScreen::HostNewGame => {
-                render_multiplayer_host_new(ui, actions, host_new_game);
+                render_multiplayer_host_new(ui, actions, host_new_game, forms);
             }
             MultiplayerMenuScreen::HostSavedGame => {
-                render_multiplayer_host_saved(ui, actions, host_saved_game);
+                render_multiplayer_host_saved(ui, actions, host_saved_game, forms);
             }
             MultiplayerMenuScreen::JoinGame => {
                 render_multiplayer_join_game(
@@ -393,6 +617,13 @@ fn render_multiplayer_menu(
                     discovered_servers,
                     discovery_control,
                     client_target,
+                    network_settings,
+                    forms,
+                );
+                compatibility::render_join_check(
+                    ui,
+                    &mut forms.join_check,
+                    forms.connection.as_deref(),
                 );
             }
         }
@@ -421,40 +652,44 @@ fn render_multiplayer_host_new(
     ui: &mut egui::Ui,
     actions: &mut MenuActions,
     state: Option<&State<HostNewGameMenuScreen>>,
+    forms: &mut MenuForms,
 ) {
-    let Some(step) = state else {
+    let Some(step) = state.map(|state| *state.get()) else {
         return;
     };
+    let current = match step {
+        HostNewGameMenuScreen::ConfigServer => 0,
+        HostNewGameMenuScreen::ConfigWorld => 1,
+        HostNewGameMenuScreen::ConfigSave => 2,
+    };
 
-    match step.get() {
+    let mut action = Wizard::new(
+        &["Configure Server", "Configure World", "Configure Save"],
+        current,
+    )
+    .finish_label("Start Server")
+    .show(ui, |ui| match step {
         HostNewGameMenuScreen::ConfigServer => {
-            ui.label("Step 1/3: Configure Server");
-            if ui.button("Next →").clicked() {
-                actions.commands.trigger(SetNewHostGame::Next);
-            }
+            host_config::render_host_config_form(ui, &mut forms.host_config)
         }
         HostNewGameMenuScreen::ConfigWorld => {
-            ui.label("Step 2/3: Configure World");
-            if ui.button("← Previous").clicked() {
-                actions.commands.trigger(SetNewHostGame::Previous);
-            }
-            if ui.button("Next →").clicked() {
-                actions.commands.trigger(SetNewHostGame::Next);
-            }
+            world_config::render_world_config_form(ui, &mut forms.world_config)
         }
         HostNewGameMenuScreen::ConfigSave => {
-            ui.label("Step 3/3: Configure Save");
-            if ui.button("← Previous").clicked() {
-                actions.commands.trigger(SetNewHostGame::Previous);
-            }
-            if ui.button("Start Server").clicked() {
-                actions.commands.trigger(SetNewHostGame::Confirm);
-            }
+            saves::render_save_config_form(ui, &mut forms.save_config)
         }
+    });
+    if step == HostNewGameMenuScreen::ConfigSave {
+        action = confirm_save_overwrite(ui, forms, action);
     }
 
-    if ui.button("Cancel").clicked() {
-        actions.commands.trigger(SetNewHostGame::Cancel);
+    if let Some(action) = action {
+        actions.commands.trigger(match action {
+            WizardAction::Previous => SetNewHostGame::Previous,
+            WizardAction::Next => SetNewHostGame::Next,
+            WizardAction::Finish => SetNewHostGame::Confirm,
+            WizardAction::Cancel => SetNewHostGame::Cancel,
+        });
     }
 }
 
@@ -462,31 +697,40 @@ fn render_multiplayer_host_saved(
     ui: &mut egui::Ui,
     actions: &mut MenuActions,
     state: Option<&State<HostSavedGameMenuScreen>>,
+    forms: &mut MenuForms,
 ) {
-    let Some(step) = state else {
+    let Some(step) = state.map(|state| *state.get()) else {
         return;
     };
+    let current = match step {
+        HostSavedGameMenuScreen::Overview => 0,
+        HostSavedGameMenuScreen::ConfigServer => 1,
+    };
 
-    match step.get() {
-        HostSavedGameMenuScreen::Overview => {
-            ui.label("Step 1/2: Select Save");
-            if ui.button("Next →").clicked() {
-                actions.commands.trigger(SetSavedHostGame::Next);
+    let action = Wizard::new(&["Select Save", "Configure Server"], current)
+        .finish_label("Start Server")
+        .show(ui, |ui| match step {
+            HostSavedGameMenuScreen::Overview => {
+                save_browser::render_save_browser(ui, &mut forms.save_browser)
             }
-        }
-        HostSavedGameMenuScreen::ConfigServer => {
-            ui.label("Step 2/2: Configure Server");
-            if ui.button("← Previous").clicked() {
-                actions.commands.trigger(SetSavedHostGame::Previous);
-            }
-            if ui.button("Start Server").clicked() {
-                actions.commands.trigger(SetSavedHostGame::Confirm);
+            HostSavedGameMenuScreen::ConfigServer => {
+                if let Some(SelectedSave(slot)) = forms.selected_save.as_deref() {
+                    ui.label(format!("Save: {}", slot.meta.name));
+                    if let Some(warning) = save_browser::version_warning(&slot.meta) {
+                        ui.colored_label(egui::Color32::from_rgb(255, 180, 80), warning);
+                    }
+                }
+                host_config::render_host_config_form(ui, &mut forms.host_config)
             }
-        }
-    }
+        });
 
-    if ui.button("Cancel").clicked() {
-        actions.commands.trigger(SetSavedHostGame::Cancel);
+    if let Some(action) = action {
+        actions.commands.trigger(match action {
+            WizardAction::Previous => SetSavedHostGame::Previous,
+            WizardAction::Next => SetSavedHostGame::Next,
+            WizardAction::Finish => SetSavedHostGame::Confirm,
+            WizardAction::Cancel => SetSavedHostGame::Cancel,
+        });
     }
 }
 
@@ -496,7 +740,10 @@ fn render_multiplayer_join_game(
     discovered_servers: Option<&DiscoveredServers>,
     mut discovery_control: Option<&mut DiscoveryControl>,
     client_target: Option<&mut ClientTarget>,
+    network_settings: &NetworkSettings,
+    forms: &mut MenuForms,
 ) {
+    let default_port = network_settings.default_port;
     ui.heading("Local Servers");
 
     ui.horizontal(|ui| {
@@ -507,7 +754,7 @@ fn render_multiplayer_join_game(
             } else {
                 ui.label("Scan finished.");
                 if ui.button("Refresh").clicked() {
-                    control.cycles_remaining = 5;
+                    control.cycles_remaining = network_settings.discovery_cycles;
                     control.timer.reset();
                 }
             }
@@ -521,14 +768,8 @@ fn render_multiplayer_join_game(
         let servers = &res.0;
         if !servers.is_empty() {
             ui.separator();
-            for server in servers {
-                if ui.selectable_label(false, server.to_string()).clicked() {
-                    // ✅ input instead of target
-                    actions.commands.queue(SetClientTarget {
-                        input: server.clone(),
-                    });
-                }
-            }
+            let action = server_query::render_server_table(ui, &mut forms.server_queries, servers);
+            handle_server_list_action(actions, &mut forms.join_address, default_port, action);
             ui.separator();
         } else if let Some(control) = discovery_control {
             // Only show "No servers found" if scan is finished
@@ -540,47 +781,42 @@ fn render_multiplayer_join_game(
 
     ui.separator();
 
-    let mut is_client_target_valid = false;
-    if let Some(target) = client_target {
-        ui.horizontal(|ui| {
-            let response =
-                ui.add(egui::TextEdit::singleline(&mut target.input).hint_text("127.0.0.1:8080"));
+    // Hostnames are resolved in the background before they reach the client target
+    let current_address = forms
+        .join_address
+        .resolved()
+        .map(|_| forms.join_address.input.trim().to_string());
+    let action = server_list::render_server_lists(
+        ui,
+        &mut forms.server_list,
+        &forms.server_queries,
+        current_address.as_deref(),
+    );
+    handle_server_list_action(actions, &mut forms.join_address, default_port, action);
 
-            if response.changed() {
-                let val = target.input.clone();
-                target.update_input(val);
-            }
+    ui.separator();
 
-            // Display status
-            ui.label(match target.is_valid {
-                true => "Valid",
-                false => {
-                    if target.input.trim().is_empty() {
-                        ""
-                    } else {
-                        "Invalid"
-                    }
-                }
-            });
-        });
+    address::render_address_input(ui, &mut forms.join_address, default_port);
+    if let Some(target) = client_target {
         ui.label(format!(
             "Client Target:\nInput:{}\nIP-Address:{:?}\nPort:{}\nIs valid:{}",
             target.input, target.ip, target.port, target.is_valid
         ));
-
-        is_client_target_valid = target.is_valid;
     }
 
     ui.separator();
 
     let join_button = ui.add_enabled(
-        is_client_target_valid,
+        current_address.is_some(),
         egui::Button::new("Join Selected Game"),
     );
 
-    if join_button.clicked() {
-        actions.commands.trigger(SetJoinGame::Confirm);
+    if join_button.clicked()
+        && let Some(address) = current_address
+    {
+        actions.commands.trigger(RequestJoin { address });
     }
+    ui.weak(compatibility::client_version_text());
 
     ui.separator();
 
@@ -589,6 +825,23 @@ fn render_multiplayer_join_game(
     }
 }
 
+fn handle_server_list_action(
+    actions: &mut MenuActions,
+    join_address: &mut JoinAddress,
+    default_port: u16,
+    action: Option<ServerListAction>,
+) {
+    match action {
+        Some(ServerListAction::Select(address)) => {
+            join_address.set_input(address, default_port);
+        }
+        Some(ServerListAction::Join(address)) => {
+            actions.commands.trigger(RequestJoin { address });
+        }
+        None => {}
+    }
+}
+
 fn render_menu_wiki(ui: &mut egui::Ui, actions: &mut MenuActions) {
     ui.vertical_centered_justified(|ui| {
         if ui.button("Back").clicked() {
@@ -597,10 +850,84 @@ fn render_menu_wiki(ui: &mut egui::Ui, actions: &mut MenuActions) {
     });
 }
 
-fn render_menu_settings(ui: &mut egui::Ui, actions: &mut MenuActions) {
+fn render_menu_settings(
+    ui: &mut egui::Ui,
+    actions: &mut MenuActions,
+    state: Option<&State<SettingsMenuScreen>>,
+    user_settings: &mut ResMut<UserSettings>,
+    binding_capture: &mut BindingCapture,
+    monitors: &[MonitorInfo],
+) {
     ui.vertical_centered_justified(|ui| {
+        let Some(screen) = state.map(|state| *state.get()) else {
+            return;
+        };
+
+        match screen {
+            SettingsMenuScreen::Overview => {
+                for category in SettingsMenuScreen::CATEGORIES {
+                    if ui.button(category.label()).clicked() {
+                        actions.commands.trigger(SetSettingsScreen::Open(category));
+                    }
+                }
+                ui.separator();
+                if ui.button("Restore All Defaults").clicked() {
+                    user_settings.restore_defaults(SettingsMenuScreen::Overview);
+                }
+            }
+            category => {
+                ui.heading(category.label());
+                ui.separator();
+
+                // Only flag real widget changes, otherwise every frame would trigger a save
+                if render_settings_category(
+                    ui,
+                    category,
+                    user_settings.bypass_change_detection(),
+                    binding_capture,
+                    monitors,
+                ) {
+                    user_settings.validate();
+                    user_settings.set_changed();
+                }
+
+                ui.separator();
+                if ui.button("Restore Defaults").clicked() {
+                    user_settings.restore_defaults(category);
+                }
+            }
+        }
+
         if ui.button("Back").clicked() {
-            actions.commands.trigger(SetSettingsMenu::Back);
+            actions.commands.trigger(SetSettingsScreen::Back);
         }
     });
 }
+
+fn render_settings_category(
+    ui: &mut egui::Ui,
+    category: SettingsMenuScreen,
+    user_settings: &mut UserSettings,
+    binding_capture: &mut BindingCapture,
+    monitors: &[MonitorInfo],
+) -> bool {
+    match category {
+        SettingsMenuScreen::Video => {
+            video::render_video_settings(ui, &mut user_settings.video, monitors)
+        }
+        SettingsMenuScreen::Audio => audio::render_audio_settings(ui, &mut user_settings.audio),
+        SettingsMenuScreen::Controls => input::render_controls_settings(
+            ui,
+            &mut user_settings.controls.bindings,
+            binding_capture,
+        ),
+        SettingsMenuScreen::Chat => settings::render_chat_settings(ui, &mut user_settings.chat),
+        SettingsMenuScreen::Network => {
+            settings::render_network_settings(ui, &mut user_settings.network)
+        }
+        SettingsMenuScreen::Interface => {
+            settings::render_interface_settings(ui, &mut user_settings.interface)
+        }
+        SettingsMenuScreen::Overview => false,
+    }
+}
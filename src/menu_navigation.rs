@@ -0,0 +1,229 @@
+use {
+    crate::input::{ActionState, InputAction, InputActionSystems},
+    bevy::prelude::*,
+    bevy_egui::{
+        EguiContext, EguiContexts, EguiInput, EguiPreUpdateSet, EguiPrimaryContextPass,
+        PrimaryEguiContext, egui,
+    },
+    chicken::states::states::{app::AppScope, session::SessionState},
+};
+
+/// Verzögerung, bis ein gehaltener Stick/D-Pad die Auswahl wiederholt bewegt
+const REPEAT_DELAY_SECS: f32 = 0.4;
+/// Abstand der Wiederholungen danach
+const REPEAT_INTERVAL_SECS: f32 = 0.12;
+/// Ab dieser Auslenkung zählt der linke Stick als Richtung
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Plugin für die Menü-Navigation per Tastatur und Gamepad.
+///
+/// egui kennt bereits Pfeiltasten, Tab und Enter/Space für den Fokus. Dieses Plugin
+/// übersetzt die Menü-Aktionen (D-Pad, Stick, umbelegte Tasten) in dieselben
+/// egui-Tastenereignisse und zeichnet einen gut sichtbaren Fokusrahmen.
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            inject_menu_navigation
+                .after(InputActionSystems)
+                .after(EguiPreUpdateSet::ProcessInput)
+                .before(EguiPreUpdateSet::BeginPass)
+                .run_if(in_menu),
+        )
+        .add_systems(EguiPrimaryContextPass, draw_focus_ring.run_if(in_menu));
+    }
+}
+
+fn in_menu(
+    app_scope: Option<Res<State<AppScope>>>,
+    session: Option<Res<State<SessionState>>>,
+) -> bool {
+    app_scope.is_some_and(|state| *state.get() == AppScope::Menu)
+        || session.is_some_and(|state| *state.get() == SessionState::Paused)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavDirection {
+    fn action(self) -> InputAction {
+        match self {
+            NavDirection::Up => InputAction::MenuUp,
+            NavDirection::Down => InputAction::MenuDown,
+            NavDirection::Left => InputAction::MenuLeft,
+            NavDirection::Right => InputAction::MenuRight,
+        }
+    }
+
+    fn egui_key(self) -> egui::Key {
+        match self {
+            NavDirection::Up => egui::Key::ArrowUp,
+            NavDirection::Down => egui::Key::ArrowDown,
+            NavDirection::Left => egui::Key::ArrowLeft,
+            NavDirection::Right => egui::Key::ArrowRight,
+        }
+    }
+
+    fn native_key(self) -> KeyCode {
+        match self {
+            NavDirection::Up => KeyCode::ArrowUp,
+            NavDirection::Down => KeyCode::ArrowDown,
+            NavDirection::Left => KeyCode::ArrowLeft,
+            NavDirection::Right => KeyCode::ArrowRight,
+        }
+    }
+
+    fn from_stick(stick: Vec2) -> Option<Self> {
+        if stick.length() < STICK_THRESHOLD {
+            None
+        } else if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0.0 {
+                NavDirection::Right
+            } else {
+                NavDirection::Left
+            })
+        } else if stick.y > 0.0 {
+            Some(NavDirection::Up)
+        } else {
+            Some(NavDirection::Down)
+        }
+    }
+}
+
+/// Zuletzt gehaltene Richtung und Timer für die Wiederholung
+#[derive(Default)]
+struct NavRepeat {
+    held: Option<NavDirection>,
+    timer: Timer,
+}
+
+fn inject_menu_navigation(
+    actions: Res<ActionState>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time<Real>>,
+    mut repeat: Local<NavRepeat>,
+    mut contexts: Query<(&mut EguiContext, &mut EguiInput), With<PrimaryEguiContext>>,
+) {
+    let Ok((mut context, mut input)) = contexts.single_mut() else {
+        return;
+    };
+    let native_pressed = |key: KeyCode| keys.as_ref().is_some_and(|keys| keys.pressed(key));
+
+    // Pfeiltasten erreichen egui ohnehin, hier nur die übrigen Quellen
+    let held = [
+        NavDirection::Up,
+        NavDirection::Down,
+        NavDirection::Left,
+        NavDirection::Right,
+    ]
+    .into_iter()
+    .find(|direction| {
+        actions.pressed(direction.action()) && !native_pressed(direction.native_key())
+    })
+    .or_else(|| {
+        gamepads
+            .iter()
+            .find_map(|gamepad| NavDirection::from_stick(gamepad.left_stick()))
+    });
+
+    let step = match held {
+        Some(direction) if repeat.held != held => {
+            repeat.timer = Timer::from_seconds(REPEAT_DELAY_SECS, TimerMode::Once);
+            Some(direction)
+        }
+        Some(direction) if repeat.timer.tick(time.delta()).just_finished() => {
+            repeat.timer = Timer::from_seconds(REPEAT_INTERVAL_SECS, TimerMode::Once);
+            Some(direction)
+        }
+        _ => None,
+    };
+    repeat.held = held;
+
+    let has_focus = context
+        .get_mut()
+        .memory(|memory| memory.focused().is_some());
+    let mut keys_to_send = Vec::new();
+
+    if let Some(direction) = step {
+        keys_to_send.push(focus_key(direction, has_focus));
+    } else if !has_focus {
+        // Ohne Fokus ignoriert egui die Pfeiltasten, daher das erste Widget wählen
+        if let Some(direction) = [
+            NavDirection::Up,
+            NavDirection::Down,
+            NavDirection::Left,
+            NavDirection::Right,
+        ]
+        .into_iter()
+        .find(|direction| {
+            keys.as_ref()
+                .is_some_and(|keys| keys.just_pressed(direction.native_key()))
+        }) {
+            keys_to_send.push(focus_key(direction, false));
+        }
+    }
+
+    if actions.just_pressed(InputAction::MenuConfirm)
+        && !native_pressed(KeyCode::Enter)
+        && !native_pressed(KeyCode::Space)
+    {
+        keys_to_send.push((egui::Key::Enter, egui::Modifiers::NONE));
+    }
+
+    for (key, modifiers) in keys_to_send {
+        for pressed in [true, false] {
+            input.events.push(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+    }
+}
+
+/// Pfeiltaste bei vorhandenem Fokus, sonst Tab bzw. Shift+Tab auf das erste/letzte Widget
+fn focus_key(direction: NavDirection, has_focus: bool) -> (egui::Key, egui::Modifiers) {
+    if has_focus {
+        (direction.egui_key(), egui::Modifiers::NONE)
+    } else if matches!(direction, NavDirection::Up | NavDirection::Left) {
+        (egui::Key::Tab, egui::Modifiers::SHIFT)
+    } else {
+        (egui::Key::Tab, egui::Modifiers::NONE)
+    }
+}
+
+/// Zeichnet einen deutlichen Rahmen um das fokussierte Widget. Die Position
+/// stammt aus dem vorherigen Frame, falls das Widget noch nicht gezeichnet wurde.
+fn draw_focus_ring(mut contexts: EguiContexts) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Some(response) = ctx
+        .memory(|memory| memory.focused())
+        .and_then(|id| ctx.read_response(id))
+    else {
+        return;
+    };
+
+    let stroke = egui::Stroke::new(2.0, ctx.style().visuals.selection.stroke.color);
+    ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("menu_focus_ring"),
+    ))
+    .rect_stroke(
+        response.rect.expand(3.0),
+        4.0,
+        stroke,
+        egui::StrokeKind::Outside,
+    );
+}
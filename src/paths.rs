@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+/// Name des Unterordners in den plattformspezifischen Verzeichnissen
+pub const APP_DIR_NAME: &str = "fos";
+
+/// Konfigurationsverzeichnis des Clients (z.B. `~/.config/fos` unter Linux).
+/// Fällt auf das Arbeitsverzeichnis zurück, wenn die Plattform keines kennt.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+}
+
+/// Datenverzeichnis des Clients (z.B. `~/.local/share/fos` unter Linux) für
+/// Spielstände und andere größere Dateien.
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR_NAME)
+}
+
+/// Verzeichnis mit einem Unterordner pro Spielstand
+pub fn saves_dir() -> PathBuf {
+    data_dir().join("saves")
+}
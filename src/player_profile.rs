@@ -0,0 +1,421 @@
+use {
+    crate::settings::SettingsPath,
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::menu::singleplayer::SetSingleplayerNewGame,
+        states::menu::singleplayer::NewGameMenuScreen,
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt, fs,
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Erlaubte Länge des Spielernamens in Zeichen
+pub const NAME_LENGTH: RangeInclusive<usize> = 3..=24;
+/// Maximale Dateigröße eines Avatars
+pub const AVATAR_MAX_BYTES: u64 = 1024 * 1024;
+const AVATAR_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+/// Plugin für das Spielerprofil aus "Configure Player".
+///
+/// Ein gültiges gespeichertes Profil gilt ab dem Start als [`PlayerProfile`], also
+/// auch beim Hosten und Beitreten. Das Formular lebt in [`PlayerProfileForm`]; beim
+/// `SetSingleplayerNewGame::Next` im ersten Schritt wird daraus das neue Profil.
+/// Muss nach dem `SettingsPlugin` hinzugefügt werden.
+pub struct PlayerProfilePlugin;
+
+impl Plugin for PlayerProfilePlugin {
+    fn build(&self, app: &mut App) {
+        let path = app
+            .world()
+            .get_resource::<SettingsPath>()
+            .cloned()
+            .unwrap_or_default();
+        let profile = PlayerProfile::load_or_default(&profile_path(&path));
+        if profile.validate().is_empty() {
+            app.insert_resource(profile.clone());
+        }
+        app.insert_resource(PlayerProfileForm::from(profile))
+            .add_observer(on_new_game_next);
+    }
+}
+
+/// Pfad der zuletzt verwendeten Profildaten, neben der Einstellungsdatei
+fn profile_path(settings: &SettingsPath) -> PathBuf {
+    settings.0.with_file_name("profile.toml")
+}
+
+/// Erscheinungsbild der Spielfigur
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Appearance {
+    #[default]
+    Standard,
+    Sturdy,
+    Slender,
+}
+
+impl Appearance {
+    pub const ALL: [Appearance; 3] = [
+        Appearance::Standard,
+        Appearance::Sturdy,
+        Appearance::Slender,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Appearance::Standard => "Standard",
+            Appearance::Sturdy => "Sturdy",
+            Appearance::Slender => "Slender",
+        }
+    }
+}
+
+/// Vom Spieler gewähltes Profil der aktuellen Session
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerProfile {
+    pub name: String,
+    /// Spielerfarbe als RGB
+    pub color: [u8; 3],
+    pub appearance: Appearance,
+    /// Optionales Avatar-Bild (PNG/JPEG)
+    pub avatar: Option<PathBuf>,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color: [70, 130, 220],
+            appearance: Appearance::default(),
+            avatar: None,
+        }
+    }
+}
+
+/// Grund, warum ein Profil nicht übernommen werden kann
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    NameLength,
+    NameCharacters,
+    AvatarMissing,
+    AvatarFormat,
+    AvatarTooLarge,
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::NameLength => write!(
+                f,
+                "Name must be {} to {} characters long",
+                NAME_LENGTH.start(),
+                NAME_LENGTH.end()
+            ),
+            ProfileError::NameCharacters => {
+                write!(
+                    f,
+                    "Name may only contain letters, digits, spaces, '_' and '-'"
+                )
+            }
+            ProfileError::AvatarMissing => write!(f, "Avatar file not found"),
+            ProfileError::AvatarFormat => write!(f, "Avatar must be a PNG or JPEG image"),
+            ProfileError::AvatarTooLarge => {
+                write!(
+                    f,
+                    "Avatar must be smaller than {} KiB",
+                    AVATAR_MAX_BYTES / 1024
+                )
+            }
+        }
+    }
+}
+
+impl PlayerProfile {
+    /// Prüft Name und Avatar; liefert alle Fehler auf einmal für die Anzeige im Formular
+    pub fn validate(&self) -> Vec<ProfileError> {
+        let mut errors = self.validate_name();
+        if let Some(avatar) = &self.avatar {
+            errors.extend(validate_avatar(avatar));
+        }
+        errors
+    }
+
+    fn validate_name(&self) -> Vec<ProfileError> {
+        let mut errors = Vec::new();
+
+        let name = self.name.trim();
+        if !NAME_LENGTH.contains(&name.chars().count()) {
+            errors.push(ProfileError::NameLength);
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-'))
+        {
+            errors.push(ProfileError::NameCharacters);
+        }
+        errors
+    }
+
+    /// Lädt das zuletzt verwendete Profil; fehlt es oder ist es kaputt, ein leeres
+    pub fn load_or_default(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).unwrap_or_else(|err| {
+                warn!("Invalid player profile {}: {err}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("serializing player profile")?;
+        fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// Spielername aus `--name`. Gilt nur für diesen Lauf: er ersetzt den Namen des
+/// Session-Profils, landet aber weder im Formular noch in `profile.toml`.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerNameOverride(pub String);
+
+impl PlayerNameOverride {
+    /// Prüft den Namen nach denselben Regeln wie das Formular
+    pub fn new(name: &str) -> Result<Self, ProfileError> {
+        let profile = PlayerProfile {
+            name: name.trim().to_string(),
+            ..default()
+        };
+        match profile.validate_name().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(Self(profile.name)),
+        }
+    }
+
+    /// Profil mit diesem Namen
+    pub fn apply(&self, profile: PlayerProfile) -> PlayerProfile {
+        PlayerProfile {
+            name: self.0.clone(),
+            ..profile
+        }
+    }
+}
+
+/// Prüft Format und Größe der Avatar-Datei
+fn validate_avatar(avatar: &Path) -> Vec<ProfileError> {
+    let mut errors = Vec::new();
+    let extension = avatar
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    if !extension.is_some_and(|ext| AVATAR_EXTENSIONS.contains(&ext.as_str())) {
+        errors.push(ProfileError::AvatarFormat);
+    }
+    match fs::metadata(avatar) {
+        Ok(meta) if meta.len() > AVATAR_MAX_BYTES => errors.push(ProfileError::AvatarTooLarge),
+        Ok(_) => {}
+        Err(_) => errors.push(ProfileError::AvatarMissing),
+    }
+    errors
+}
+
+/// Formularzustand von "Configure Player"; bleibt beim Zurückblättern erhalten
+#[derive(Resource, Debug, Default)]
+pub struct PlayerProfileForm {
+    pub profile: PlayerProfile,
+    /// Texteingabe für den Avatar-Pfad, leer = kein Avatar
+    pub avatar_input: String,
+    /// Ergebnis der Avatar-Prüfung für den zuletzt geprüften Pfad
+    avatar_check: Option<(String, Vec<ProfileError>)>,
+}
+
+impl From<PlayerProfile> for PlayerProfileForm {
+    fn from(profile: PlayerProfile) -> Self {
+        let avatar_input = profile
+            .avatar
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        Self {
+            profile,
+            avatar_input,
+            avatar_check: None,
+        }
+    }
+}
+
+impl PlayerProfileForm {
+    /// Profil mit getrimmtem Namen und übernommenem Avatar-Pfad
+    pub fn to_profile(&self) -> PlayerProfile {
+        let avatar = self.avatar_input.trim();
+        PlayerProfile {
+            name: self.profile.name.trim().to_string(),
+            avatar: (!avatar.is_empty()).then(|| PathBuf::from(avatar)),
+            ..self.profile.clone()
+        }
+    }
+
+    /// Fehler für die Anzeige; die Avatar-Datei wird nur geprüft, wenn sich der Pfad
+    /// geändert hat, nicht in jedem Frame
+    pub fn errors(&mut self) -> Vec<ProfileError> {
+        let mut errors = self.to_profile().validate_name();
+        let avatar = self.avatar_input.trim();
+        if avatar.is_empty() {
+            return errors;
+        }
+        if self
+            .avatar_check
+            .as_ref()
+            .is_none_or(|(checked, _)| checked != avatar)
+        {
+            self.avatar_check = Some((avatar.to_string(), validate_avatar(Path::new(avatar))));
+        }
+        if let Some((_, avatar_errors)) = &self.avatar_check {
+            errors.extend(avatar_errors.iter().cloned());
+        }
+        errors
+    }
+}
+
+/// Übernimmt das Formular beim Weiterblättern aus "Configure Player" in die Session.
+/// Mit [`PlayerNameOverride`] spielt die Session unter diesem Namen; gespeichert wird
+/// nur das Formular, und auch nur, wenn es für sich gültig ist.
+fn on_new_game_next(
+    event: On<SetSingleplayerNewGame>,
+    mut commands: Commands,
+    step: Option<Res<State<NewGameMenuScreen>>>,
+    form: Res<PlayerProfileForm>,
+    name_override: Option<Res<PlayerNameOverride>>,
+    settings_path: Res<SettingsPath>,
+) {
+    if !matches!(*event, SetSingleplayerNewGame::Next)
+        || step.is_none_or(|step| *step.get() != NewGameMenuScreen::ConfigPlayer)
+    {
+        return;
+    }
+
+    let profile = form.to_profile();
+    let form_valid = profile.validate().is_empty();
+    let session = match &name_override {
+        Some(name) => name.apply(profile.clone()),
+        None => profile.clone(),
+    };
+    if !session.validate().is_empty() {
+        warn!("Player profile is invalid, keeping previous identity");
+        return;
+    }
+    if form_valid && let Err(err) = profile.save(&profile_path(&settings_path)) {
+        warn!("Could not save player profile: {err:#}");
+    }
+    commands.insert_resource(session);
+}
+
+// --- UI ---
+
+/// Rendert das Formular. Gibt `true` zurück, wenn das Profil gültig ist.
+pub fn render_player_profile_form(ui: &mut egui::Ui, form: &mut PlayerProfileForm) -> bool {
+    egui::Grid::new("player_profile_form")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.profile.name)
+                    .char_limit(*NAME_LENGTH.end())
+                    .hint_text("Your player name"),
+            );
+            ui.end_row();
+
+            ui.label("Color");
+            ui.color_edit_button_srgb(&mut form.profile.color);
+            ui.end_row();
+
+            ui.label("Appearance");
+            egui::ComboBox::from_id_salt("player_appearance")
+                .selected_text(form.profile.appearance.label())
+                .show_ui(ui, |ui| {
+                    for appearance in Appearance::ALL {
+                        ui.selectable_value(
+                            &mut form.profile.appearance,
+                            appearance,
+                            appearance.label(),
+                        );
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Avatar (optional)");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.avatar_input).hint_text("/path/to/avatar.png"),
+            );
+            ui.end_row();
+        });
+
+    let errors = form.errors();
+    for error in &errors {
+        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error.to_string());
+    }
+    errors.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> PlayerProfile {
+        PlayerProfile {
+            name: name.to_string(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn valid_names_pass() {
+        assert!(profile("Ada").validate().is_empty());
+        assert!(profile("Jörg_the-2nd").validate().is_empty());
+    }
+
+    #[test]
+    fn invalid_names_are_reported() {
+        assert_eq!(profile("  ab ").validate(), vec![ProfileError::NameLength]);
+        assert_eq!(
+            profile("@admin").validate(),
+            vec![ProfileError::NameCharacters]
+        );
+    }
+
+    #[test]
+    fn avatar_must_exist_and_be_an_image() {
+        let mut with_avatar = profile("Ada");
+        with_avatar.avatar = Some(PathBuf::from("does/not/exist.gif"));
+        assert_eq!(
+            with_avatar.validate(),
+            vec![ProfileError::AvatarFormat, ProfileError::AvatarMissing]
+        );
+    }
+
+    #[test]
+    fn name_override_only_replaces_the_name() {
+        assert_eq!(
+            PlayerNameOverride::new(" x "),
+            Err(ProfileError::NameLength)
+        );
+
+        let name = PlayerNameOverride::new(" Alice ").unwrap();
+        let saved = PlayerProfile {
+            color: [1, 2, 3],
+            ..profile("Bob")
+        };
+        let session = name.apply(saved.clone());
+        assert_eq!(session.name, "Alice");
+        assert_eq!(session.color, saved.color);
+    }
+}
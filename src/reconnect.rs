@@ -0,0 +1,328 @@
+use {
+    crate::{
+        address::JoinAddress,
+        compatibility::JoinCheck,
+        connection::ConnectionAttempt,
+        settings::{NetworkSettings, UserSettings},
+    },
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::session::SetPauseMenu,
+        states::{app::AppScope, session::ClientConnectionStatus},
+    },
+    std::{
+        hash::BuildHasher,
+        time::{Duration, Instant},
+    },
+};
+
+/// Obergrenze für die Wartezeit zwischen zwei Versuchen
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Ein einzelner Versuch gilt nach dieser Zeit als gescheitert
+pub const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Plugin für das automatische Wiederverbinden nach einem Verbindungsabbruch.
+///
+/// Verlässt der Client `ClientConnectionStatus::Playing`, ohne dass der Spieler die
+/// Session beendet hat, wird der Beitritt mit exponentiellem Backoff wiederholt.
+/// chicken startet einen Beitritt nur aus "Join Game" heraus, die Session wird
+/// dafür also verlassen: Der Chat ist bis zum erneuten Beitritt nicht nutzbar,
+/// der Verlauf in `ChatState` bleibt erhalten. Scheitern alle Versuche,
+/// zeigt das Menü eine Zusammenfassung.
+pub struct ReconnectPlugin;
+
+impl Plugin for ReconnectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReconnectState>()
+            .add_observer(on_pause_exit)
+            .add_systems(OnEnter(ClientConnectionStatus::Playing), on_connected)
+            .add_systems(OnExit(ClientConnectionStatus::Playing), on_connection_lost)
+            .add_systems(
+                Update,
+                drive_reconnect.run_if(resource_exists::<Reconnecting>),
+            );
+    }
+}
+
+/// Merkt sich, wohin zuletzt verbunden wurde und ob der Spieler selbst gegangen ist
+#[derive(Resource, Debug, Default)]
+struct ReconnectState {
+    address: Option<String>,
+    user_left: bool,
+}
+
+/// Laufendes Wiederverbinden
+#[derive(Resource, Debug)]
+pub struct Reconnecting {
+    pub address: String,
+    /// Anzahl der bisher gestarteten Versuche
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Zeitpunkt des nächsten Versuchs; `None`, solange ein Versuch läuft
+    pub next_try: Option<Instant>,
+    /// Fehlergründe der gescheiterten Versuche
+    pub failures: Vec<String>,
+    pub since: Instant,
+    attempt_started: Option<Instant>,
+    exit_requested: bool,
+    stop_requested: bool,
+}
+
+impl Reconnecting {
+    pub fn new(address: String, max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            address,
+            attempt: 0,
+            max_attempts,
+            base_delay,
+            next_try: Some(Instant::now() + backoff(base_delay, 0, jitter())),
+            failures: Vec::new(),
+            since: Instant::now(),
+            attempt_started: None,
+            exit_requested: false,
+            stop_requested: false,
+        }
+    }
+}
+
+/// Ergebnis eines gescheiterten Wiederverbindens; wird im Menü angezeigt
+#[derive(Resource, Debug)]
+pub struct ReconnectSummary {
+    pub address: String,
+    pub attempts: u32,
+    pub failures: Vec<String>,
+    pub duration: Duration,
+}
+
+/// Wartezeit vor Versuch `attempt` (ab 0): `base * 2^attempt`, höchstens
+/// [`MAX_BACKOFF`], um `jitter` (-1..=1) um bis zu 25% verschoben
+pub fn backoff(base: Duration, attempt: u32, jitter: f32) -> Duration {
+    let delay = base
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_BACKOFF);
+    delay.mul_f32(1.0 + jitter.clamp(-1.0, 1.0) * 0.25)
+}
+
+/// Zufallswert in -1..=1; verhindert, dass viele Clients gleichzeitig anklopfen
+fn jitter() -> f32 {
+    let hash = std::collections::hash_map::RandomState::new().hash_one(Instant::now());
+    (hash % 2001) as f32 / 1000.0 - 1.0
+}
+
+fn on_pause_exit(event: On<SetPauseMenu>, mut state: ResMut<ReconnectState>) {
+    if matches!(*event, SetPauseMenu::Exit) {
+        state.user_left = true;
+    }
+}
+
+fn on_connected(
+    mut commands: Commands,
+    mut state: ResMut<ReconnectState>,
+    join: Res<JoinAddress>,
+    reconnecting: Option<Res<Reconnecting>>,
+) {
+    state.address = Some(join.input.trim().to_string()).filter(|address| !address.is_empty());
+    state.user_left = false;
+    if let Some(reconnecting) = reconnecting {
+        info!(
+            "Reconnected to {} after {} attempt(s)",
+            reconnecting.address, reconnecting.attempt
+        );
+        commands.remove_resource::<Reconnecting>();
+    }
+}
+
+fn on_connection_lost(
+    mut commands: Commands,
+    state: Res<ReconnectState>,
+    settings: Res<UserSettings>,
+    reconnecting: Option<Res<Reconnecting>>,
+) {
+    let max_attempts = settings.network.reconnect_attempts;
+    if state.user_left || reconnecting.is_some() || max_attempts == 0 {
+        return;
+    }
+    let Some(address) = state.address.clone() else {
+        return;
+    };
+    warn!("Lost connection to {address}, reconnecting");
+    let base_delay = Duration::try_from_secs_f32(settings.network.reconnect_delay_secs)
+        .unwrap_or_else(|err| {
+            warn!("Invalid reconnect delay: {err}, using default");
+            Duration::from_secs_f32(NetworkSettings::default().reconnect_delay_secs)
+        });
+    commands.insert_resource(Reconnecting::new(address, max_attempts, base_delay));
+}
+
+/// Startet fällige Versuche und wertet laufende aus
+fn drive_reconnect(
+    mut commands: Commands,
+    mut reconnecting: ResMut<Reconnecting>,
+    attempt: Option<Res<ConnectionAttempt>>,
+    mut check: ResMut<JoinCheck>,
+    scope: Res<State<AppScope>>,
+) {
+    let in_session = *scope.get() == AppScope::Session;
+    if reconnecting.stop_requested {
+        info!("Stopped reconnecting to {}", reconnecting.address);
+        if in_session {
+            commands.trigger(SetPauseMenu::Exit);
+        }
+        if attempt.is_some_and(|attempt| attempt.automatic) {
+            commands.remove_resource::<ConnectionAttempt>();
+        }
+        commands.remove_resource::<Reconnecting>();
+        return;
+    }
+
+    if let Some(due) = reconnecting.next_try {
+        // Neue Versuche starten aus "Join Game"; eine verwaiste Session wird vorher beendet
+        if in_session {
+            if !reconnecting.exit_requested {
+                reconnecting.exit_requested = true;
+                commands.trigger(SetPauseMenu::Exit);
+            }
+            return;
+        }
+        if Instant::now() < due {
+            return;
+        }
+        reconnecting.attempt += 1;
+        reconnecting.next_try = None;
+        reconnecting.attempt_started = Some(Instant::now());
+        reconnecting.exit_requested = false;
+        info!(
+            "Reconnecting to {} (attempt {}/{})",
+            reconnecting.address, reconnecting.attempt, reconnecting.max_attempts
+        );
+        commands.insert_resource(ConnectionAttempt::automatic(reconnecting.address.clone()));
+        return;
+    }
+
+    let failure = if let JoinCheck::Rejected { reason, .. } = &*check {
+        let reason = reason.clone();
+        *check = JoinCheck::Idle;
+        reason
+    } else if let Some(reason) = attempt.as_ref().and_then(|attempt| attempt.failure.clone()) {
+        reason
+    } else if attempt.is_none() {
+        // Vom Spieler abgebrochen
+        info!("Reconnect to {} cancelled", reconnecting.address);
+        commands.remove_resource::<Reconnecting>();
+        return;
+    } else if reconnecting
+        .attempt_started
+        .is_some_and(|started| started.elapsed() > ATTEMPT_TIMEOUT)
+    {
+        format!("No connection after {} seconds", ATTEMPT_TIMEOUT.as_secs())
+    } else {
+        return;
+    };
+
+    warn!(
+        "Reconnect attempt {}/{} failed: {failure}",
+        reconnecting.attempt, reconnecting.max_attempts
+    );
+    reconnecting.failures.push(failure);
+    commands.remove_resource::<ConnectionAttempt>();
+
+    if reconnecting.attempt >= reconnecting.max_attempts {
+        commands.insert_resource(ReconnectSummary {
+            address: reconnecting.address.clone(),
+            attempts: reconnecting.attempt,
+            failures: std::mem::take(&mut reconnecting.failures),
+            duration: reconnecting.since.elapsed(),
+        });
+        commands.remove_resource::<Reconnecting>();
+        if in_session {
+            commands.trigger(SetPauseMenu::Exit);
+        }
+        return;
+    }
+    let delay = backoff(reconnecting.base_delay, reconnecting.attempt, jitter());
+    reconnecting.next_try = Some(Instant::now() + delay);
+}
+
+// --- UI ---
+
+/// Hinweis oben im Bild, solange wiederverbunden wird
+pub fn render_reconnect_overlay(ctx: &egui::Context, reconnecting: &mut Reconnecting) {
+    egui::Window::new("Connection lost")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 16.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            match reconnecting.next_try {
+                Some(due) => {
+                    let remaining = due.saturating_duration_since(Instant::now());
+                    ui.label(format!(
+                        "Reconnecting to {} in {:.0}s (attempt {}/{})",
+                        reconnecting.address,
+                        remaining.as_secs_f32().ceil(),
+                        reconnecting.attempt + 1,
+                        reconnecting.max_attempts
+                    ));
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Spinner::new());
+                        ui.label(format!(
+                            "Reconnecting… (attempt {}/{})",
+                            reconnecting.attempt, reconnecting.max_attempts
+                        ));
+                    });
+                }
+            }
+            if let Some(last) = reconnecting.failures.last() {
+                ui.weak(format!("Last error: {last}"));
+            }
+            if ui.button("Stop").clicked() {
+                reconnecting.stop_requested = true;
+            }
+        });
+    ctx.request_repaint_after(Duration::from_millis(250));
+}
+
+/// Zusammenfassung nach dem letzten gescheiterten Versuch. Gibt `true` zurück, wenn
+/// der Spieler sie schließt.
+pub fn render_reconnect_summary(ctx: &egui::Context, summary: &ReconnectSummary) -> bool {
+    let mut closed = false;
+    egui::Modal::new(egui::Id::new("reconnect_summary")).show(ctx, |ui| {
+        ui.heading(format!("Could not reconnect to {}", summary.address));
+        ui.label(format!(
+            "Gave up after {} attempt(s) over {:.0}s.",
+            summary.attempts,
+            summary.duration.as_secs_f32()
+        ));
+        ui.separator();
+        for (attempt, failure) in summary.failures.iter().enumerate() {
+            ui.label(format!("{}. {failure}", attempt + 1));
+        }
+        ui.separator();
+        if ui.button("OK").clicked() {
+            closed = true;
+        }
+    });
+    closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let base = Duration::from_secs(1);
+        assert_eq!(backoff(base, 0, 0.0), Duration::from_secs(1));
+        assert_eq!(backoff(base, 3, 0.0), Duration::from_secs(8));
+        assert_eq!(backoff(base, 10, 0.0), MAX_BACKOFF);
+        assert_eq!(backoff(base, u32::MAX, 0.0), MAX_BACKOFF);
+
+        assert_eq!(backoff(base, 2, 1.0), Duration::from_secs(5));
+        assert_eq!(backoff(base, 2, -1.0), Duration::from_secs(3));
+        assert!(backoff(base, 10, 1.0) <= MAX_BACKOFF.mul_f32(1.25));
+    }
+}
@@ -0,0 +1,612 @@
+use {
+    crate::{
+        config::VERSION,
+        paths, save_thumbnails,
+        saves::{self, ActiveSave, SaveMeta, SaveSlot, validate_save_name},
+    },
+    anyhow::{Context, bail},
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::menu::{multiplayer::SetSavedHostGame, singleplayer::SetSingleplayerSavedGame},
+        states::menu::{
+            multiplayer::HostSavedGameMenuScreen, singleplayer::SingleplayerMenuScreen,
+        },
+    },
+    std::{
+        cmp::Ordering,
+        collections::HashMap,
+        fs,
+        path::{Path, PathBuf},
+        process,
+    },
+};
+
+/// Plugin für die Spielstandauswahl in "Load Game" und "Host Saved Game".
+///
+/// Die Liste wird beim Betreten der Bildschirme neu eingelesen. Beim Laden wird der
+/// gewählte Spielstand sofort zum [`ActiveSave`]; beim Hosten wird er mit
+/// `SetSavedHostGame::Next` als [`SelectedSave`] in die Server-Konfiguration
+/// mitgenommen und erst beim `Confirm` aktiv.
+pub struct SaveBrowserPlugin;
+
+impl Plugin for SaveBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveBrowser::new(paths::saves_dir()))
+            .add_observer(on_load_confirm)
+            .add_observer(on_host_saved_game)
+            .add_systems(OnEnter(SingleplayerMenuScreen::LoadGame), refresh_browser)
+            .add_systems(OnEnter(HostSavedGameMenuScreen::Overview), refresh_browser);
+    }
+}
+
+/// Spalte, nach der die Liste sortiert wird
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveSort {
+    Name,
+    Seed,
+    Playtime,
+    #[default]
+    LastPlayed,
+    Version,
+}
+
+impl SaveSort {
+    fn compare(self, a: &SaveMeta, b: &SaveMeta) -> Ordering {
+        match self {
+            SaveSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SaveSort::Seed => a.world.seed.cmp(&b.world.seed),
+            SaveSort::Playtime => a.playtime_secs.cmp(&b.playtime_secs),
+            SaveSort::LastPlayed => a.last_played.cmp(&b.last_played),
+            SaveSort::Version => a.game_version.cmp(&b.game_version),
+        }
+    }
+}
+
+const WARNING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 180, 80);
+/// Anzeigegröße der Vorschaubilder in der Liste
+const THUMBNAIL_PREVIEW: egui::Vec2 = egui::vec2(64.0, 36.0);
+
+/// Zustand der Spielstandliste
+#[derive(Resource)]
+pub struct SaveBrowser {
+    pub search: String,
+    pub sort: SaveSort,
+    pub descending: bool,
+    saves_dir: PathBuf,
+    saves: Vec<SaveSlot>,
+    selected: Option<PathBuf>,
+    /// Neuer Name während des Umbenennens
+    rename_input: Option<String>,
+    confirm_delete: bool,
+    status: Option<String>,
+    /// Geladene Vorschaubilder je Spielstand, `None` = keins vorhanden
+    thumbnails: HashMap<PathBuf, Option<egui::TextureHandle>>,
+}
+
+impl SaveBrowser {
+    pub fn new(saves_dir: PathBuf) -> Self {
+        Self {
+            search: String::new(),
+            sort: SaveSort::default(),
+            descending: true,
+            saves_dir,
+            saves: Vec::new(),
+            selected: None,
+            rename_input: None,
+            confirm_delete: false,
+            status: None,
+            thumbnails: HashMap::new(),
+        }
+    }
+
+    /// Liest die Spielstände neu ein; eine nicht mehr vorhandene Auswahl wird verworfen
+    pub fn refresh(&mut self) {
+        self.saves = saves::list_saves(&self.saves_dir);
+        self.thumbnails.clear();
+        if self
+            .selected
+            .as_ref()
+            .is_some_and(|selected| !self.saves.iter().any(|slot| &slot.dir == selected))
+        {
+            self.selected = None;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&SaveSlot> {
+        let selected = self.selected.as_ref()?;
+        self.saves.iter().find(|slot| &slot.dir == selected)
+    }
+
+    /// Wählt den Spielstand mit diesem Namen oder Ordnernamen aus (ohne
+    /// Groß-/Kleinschreibung). Gibt `false` zurück, wenn es keinen gibt.
+    pub fn select_by_name(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        let found = self.saves.iter().find(|slot| {
+            slot.meta.name.eq_ignore_ascii_case(name)
+                || slot
+                    .dir
+                    .file_name()
+                    .is_some_and(|dir| dir.eq_ignore_ascii_case(name))
+        });
+        match found {
+            Some(slot) => {
+                self.selected = Some(slot.dir.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gefilterte und sortierte Spielstände für die Anzeige
+    pub fn visible(&self) -> Vec<&SaveSlot> {
+        let search = self.search.trim().to_lowercase();
+        let mut visible: Vec<_> = self
+            .saves
+            .iter()
+            .filter(|slot| {
+                search.is_empty()
+                    || slot.meta.name.to_lowercase().contains(&search)
+                    || slot.meta.world.seed.to_string().contains(&search)
+            })
+            .collect();
+        visible.sort_by(|a, b| {
+            let ordering = self.sort.compare(&a.meta, &b.meta);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        visible
+    }
+
+    fn thumbnail(&mut self, ctx: &egui::Context, dir: &Path) -> Option<egui::TextureHandle> {
+        self.thumbnails
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| save_thumbnails::load_thumbnail(ctx, dir))
+            .clone()
+    }
+
+    fn sort_by(&mut self, sort: SaveSort) {
+        if self.sort == sort {
+            self.descending = !self.descending;
+        } else {
+            self.sort = sort;
+            self.descending = matches!(sort, SaveSort::Playtime | SaveSort::LastPlayed);
+        }
+    }
+
+    fn rename_selected(&mut self, new_name: &str) -> anyhow::Result<()> {
+        let slot = self.selected().context("no save selected")?.clone();
+        let new_name = validate_save_name(new_name).map_err(|err| anyhow::anyhow!("{err}"))?;
+        let target = self.saves_dir.join(new_name);
+
+        // Nur Groß-/Kleinschreibung geändert: der Ordner ist derselbe
+        if target.exists() && !same_dir(&target, &slot.dir) {
+            bail!("A save named \"{new_name}\" already exists");
+        }
+        fs::rename(&slot.dir, &target)
+            .with_context(|| format!("renaming {}", slot.dir.display()))?;
+
+        let meta = SaveMeta {
+            name: new_name.to_string(),
+            ..slot.meta
+        };
+        meta.save(&target)?;
+        self.selected = Some(target);
+        self.refresh();
+        Ok(())
+    }
+
+    fn duplicate_selected(&mut self) -> anyhow::Result<String> {
+        let slot = self.selected().context("no save selected")?.clone();
+        let name = (1..)
+            .map(|n| match n {
+                1 => format!("{} (copy)", slot.meta.name),
+                n => format!("{} (copy {n})", slot.meta.name),
+            })
+            .find(|name| !self.saves_dir.join(name).exists())
+            .context("no free name for the copy")?;
+        let name = validate_save_name(&name)
+            .map_err(|err| anyhow::anyhow!("{err}"))?
+            .to_string();
+        let target = self.saves_dir.join(&name);
+
+        copy_dir(&slot.dir, &target)?;
+        let meta = SaveMeta {
+            name: name.clone(),
+            created: saves::unix_now(),
+            ..slot.meta
+        };
+        meta.save(&target)?;
+        self.selected = Some(target);
+        self.refresh();
+        Ok(name)
+    }
+
+    fn delete_selected(&mut self) -> anyhow::Result<()> {
+        let dir = self.selected.take().context("no save selected")?;
+        fs::remove_dir_all(&dir).with_context(|| format!("deleting {}", dir.display()))?;
+        self.refresh();
+        Ok(())
+    }
+
+    fn set_result(&mut self, result: anyhow::Result<String>) {
+        self.status = Some(match result {
+            Ok(message) => message,
+            Err(err) => format!("Error: {err:#}"),
+        });
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("creating {}", to.display()))?;
+    for entry in fs::read_dir(from).with_context(|| format!("reading {}", from.display()))? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("copying {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Öffnet den Ordner im Dateimanager des Systems
+fn open_folder(dir: &Path) -> anyhow::Result<()> {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    process::Command::new(program)
+        .arg(dir)
+        .spawn()
+        .with_context(|| format!("starting {program}"))?;
+    Ok(())
+}
+
+fn refresh_browser(mut browser: ResMut<SaveBrowser>) {
+    browser.refresh();
+}
+
+/// Für das Hosten ausgewählter Spielstand zwischen "Select Save" und "Configure Server"
+#[derive(Resource, Debug, Clone)]
+pub struct SelectedSave(pub SaveSlot);
+
+/// Macht den gewählten Spielstand beim Laden zum aktiven Spielstand
+fn on_load_confirm(
+    event: On<SetSingleplayerSavedGame>,
+    commands: Commands,
+    browser: Res<SaveBrowser>,
+) {
+    if !matches!(*event, SetSingleplayerSavedGame::Confirm) {
+        return;
+    }
+    match browser.selected() {
+        Some(slot) => activate_save(commands, slot),
+        None => warn!("No save selected, nothing to load"),
+    }
+}
+
+fn on_host_saved_game(
+    event: On<SetSavedHostGame>,
+    mut commands: Commands,
+    step: Option<Res<State<HostSavedGameMenuScreen>>>,
+    browser: Res<SaveBrowser>,
+    selected: Option<Res<SelectedSave>>,
+) {
+    match *event {
+        SetSavedHostGame::Next
+            if step.is_some_and(|step| *step.get() == HostSavedGameMenuScreen::Overview) =>
+        {
+            match browser.selected() {
+                Some(slot) => commands.insert_resource(SelectedSave(slot.clone())),
+                None => warn!("No save selected for hosting"),
+            }
+        }
+        SetSavedHostGame::Confirm => match selected {
+            Some(selected) => activate_save(commands, &selected.0),
+            None => warn!("No save selected, server starts without save metadata"),
+        },
+        SetSavedHostGame::Cancel => commands.remove_resource::<SelectedSave>(),
+        _ => {}
+    }
+}
+
+fn activate_save(mut commands: Commands, slot: &SaveSlot) {
+    if !slot.meta.is_current_version() {
+        warn!(
+            "Save {} was written by version {}, running {VERSION}",
+            slot.dir.display(),
+            slot.meta.game_version
+        );
+    }
+    info!("Loading save {}", slot.dir.display());
+    commands.insert_resource(ActiveSave::new(slot.dir.clone(), slot.meta.clone()));
+}
+
+/// Hinweis für Spielstände einer anderen Version; `None`, wenn sie passt
+pub fn version_warning(meta: &SaveMeta) -> Option<String> {
+    (!meta.is_current_version()).then(|| {
+        format!(
+            "\"{}\" was saved with version {} (this client: {VERSION}). It may not load correctly.",
+            meta.name, meta.game_version
+        )
+    })
+}
+
+/// Spielzeit als "2h 05m" bzw. "12m"
+pub fn format_playtime(secs: u64) -> String {
+    let minutes = secs / 60;
+    match minutes / 60 {
+        0 => format!("{minutes}m"),
+        hours => format!("{hours}h {:02}m", minutes % 60),
+    }
+}
+
+/// Unix-Zeit als "YYYY-MM-DD HH:MM" (UTC)
+pub fn format_timestamp(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // Kalenderdatum aus Tagen seit 1970-01-01 (proleptischer Gregorianischer Kalender)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60
+    )
+}
+
+// --- UI ---
+
+/// Rendert die Spielstandliste mit Aktionen. Gibt `true` zurück, wenn ein
+/// Spielstand ausgewählt ist.
+pub fn render_save_browser(ui: &mut egui::Ui, browser: &mut SaveBrowser) -> bool {
+    ui.horizontal(|ui| {
+        ui.label("Search");
+        ui.add(egui::TextEdit::singleline(&mut browser.search).hint_text("Name or seed"));
+        if ui.button("⟳").on_hover_text("Refresh").clicked() {
+            browser.refresh();
+        }
+    });
+
+    if browser.saves.is_empty() {
+        ui.label("No saves yet. Start a new game to create one.");
+        return false;
+    }
+
+    let visible: Vec<SaveSlot> = browser.visible().into_iter().cloned().collect();
+    let mut clicked = None;
+    let mut sort = None;
+    egui::ScrollArea::vertical()
+        .max_height(240.0)
+        .show(ui, |ui| {
+            egui::Grid::new("save_browser")
+                .num_columns(6)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    for (column, label) in [
+                        (SaveSort::Name, "Name"),
+                        (SaveSort::Seed, "Seed"),
+                        (SaveSort::Playtime, "Playtime"),
+                        (SaveSort::LastPlayed, "Last played (UTC)"),
+                        (SaveSort::Version, "Version"),
+                    ] {
+                        let arrow = match (browser.sort == column, browser.descending) {
+                            (false, _) => "",
+                            (true, true) => " ⏷",
+                            (true, false) => " ⏶",
+                        };
+                        if ui.button(format!("{label}{arrow}")).clicked() {
+                            sort = Some(column);
+                        }
+                    }
+                    ui.end_row();
+
+                    for slot in &visible {
+                        match browser.thumbnail(ui.ctx(), &slot.dir) {
+                            Some(texture) => {
+                                ui.add(
+                                    egui::Image::new(&texture).fit_to_exact_size(THUMBNAIL_PREVIEW),
+                                );
+                            }
+                            None => render_thumbnail_placeholder(ui),
+                        }
+                        let selected = browser.selected.as_ref() == Some(&slot.dir);
+                        if ui.selectable_label(selected, &slot.meta.name).clicked() {
+                            clicked = Some(slot.dir.clone());
+                        }
+                        ui.label(slot.meta.world.seed.to_string());
+                        ui.label(format_playtime(slot.meta.playtime_secs));
+                        ui.label(format_timestamp(slot.meta.last_played));
+                        if slot.meta.is_current_version() {
+                            ui.label(&slot.meta.game_version);
+                        } else {
+                            ui.colored_label(
+                                WARNING_COLOR,
+                                format!("⚠ {}", slot.meta.game_version),
+                            )
+                            .on_hover_text(format!("This client is version {VERSION}"));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(column) = sort {
+        browser.sort_by(column);
+    }
+    if let Some(dir) = clicked {
+        browser.selected = Some(dir);
+        browser.rename_input = None;
+        browser.status = None;
+    }
+
+    if let Some(warning) = browser
+        .selected()
+        .and_then(|slot| version_warning(&slot.meta))
+    {
+        ui.colored_label(WARNING_COLOR, warning);
+    }
+    render_selected_actions(ui, browser);
+
+    if let Some(status) = &browser.status {
+        ui.label(status);
+    }
+    browser.selected().is_some()
+}
+
+fn render_thumbnail_placeholder(ui: &mut egui::Ui) {
+    let (rect, _) = ui.allocate_exact_size(THUMBNAIL_PREVIEW, egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, ui.visuals().faint_bg_color);
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        "No preview",
+        egui::FontId::proportional(10.0),
+        ui.visuals().weak_text_color(),
+    );
+}
+
+fn render_selected_actions(ui: &mut egui::Ui, browser: &mut SaveBrowser) {
+    let Some(slot) = browser.selected().cloned() else {
+        return;
+    };
+
+    if let Some(mut new_name) = browser.rename_input.take() {
+        let mut keep_editing = true;
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut new_name).char_limit(saves::SAVE_NAME_MAX_LENGTH),
+            );
+            let valid = validate_save_name(&new_name);
+            if ui
+                .add_enabled(valid.is_ok(), egui::Button::new("OK"))
+                .clicked()
+            {
+                let result = browser
+                    .rename_selected(&new_name)
+                    .map(|()| format!("Renamed to \"{}\"", new_name.trim()));
+                browser.set_result(result);
+                keep_editing = false;
+            }
+            if ui.button("Cancel").clicked() {
+                keep_editing = false;
+            }
+            if let Err(err) = valid {
+                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), err.to_string());
+            }
+        });
+        if keep_editing {
+            browser.rename_input = Some(new_name);
+        }
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Rename").clicked() {
+            browser.rename_input = Some(slot.meta.name.clone());
+        }
+        if ui.button("Duplicate").clicked() {
+            let result = browser
+                .duplicate_selected()
+                .map(|name| format!("Created \"{name}\""));
+            browser.set_result(result);
+        }
+        if ui.button("Delete").clicked() {
+            browser.confirm_delete = true;
+        }
+        if ui.button("Open folder").clicked()
+            && let Err(err) = open_folder(&slot.dir)
+        {
+            browser.status = Some(format!("Error: {err:#}"));
+        }
+    });
+
+    if browser.confirm_delete {
+        egui::Modal::new(egui::Id::new("save_delete")).show(ui.ctx(), |ui| {
+            ui.heading("Delete save?");
+            ui.label(format!(
+                "\"{}\" will be deleted permanently.",
+                slot.meta.name
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("Delete").clicked() {
+                    browser.confirm_delete = false;
+                    let result = browser
+                        .delete_selected()
+                        .map(|()| format!("Deleted \"{}\"", slot.meta.name));
+                    browser.set_result(result);
+                }
+                if ui.button("Cancel").clicked() {
+                    browser.confirm_delete = false;
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::world_config::WorldConfig};
+
+    #[test]
+    fn timestamps_and_playtime_are_formatted() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_792_412_580), "2026-10-19 12:23");
+        assert_eq!(format_playtime(59), "0m");
+        assert_eq!(format_playtime(7_500), "2h 05m");
+    }
+
+    #[test]
+    fn saves_are_filtered_and_sorted() {
+        let slot = |name: &str, last_played| SaveSlot {
+            dir: PathBuf::from(name),
+            meta: SaveMeta {
+                last_played,
+                ..SaveMeta::new(name.to_string(), WorldConfig::default(), 0)
+            },
+        };
+        let mut browser = SaveBrowser::new(PathBuf::new());
+        browser.saves = vec![slot("beta", 20), slot("Alpha", 10), slot("alpine", 30)];
+
+        let names = |browser: &SaveBrowser| {
+            browser
+                .visible()
+                .iter()
+                .map(|slot| slot.meta.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&browser), ["alpine", "beta", "Alpha"]);
+
+        browser.sort_by(SaveSort::Name);
+        browser.search = "alp".to_string();
+        assert_eq!(names(&browser), ["Alpha", "alpine"]);
+    }
+}
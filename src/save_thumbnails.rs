@@ -0,0 +1,197 @@
+use {
+    crate::saves::SaveWritten,
+    anyhow::Context,
+    bevy::{
+        prelude::*,
+        render::view::screenshot::{Screenshot, ScreenshotCaptured},
+        window::PrimaryWindow,
+    },
+    bevy_egui::egui,
+    chicken::states::states::{app::AppScope, session::SessionState},
+    image::RgbImage,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Dateiname des Vorschaubilds im Ordner eines Spielstands
+pub const THUMBNAIL_FILE: &str = "thumbnail.png";
+/// Maximale Größe des Vorschaubilds; das Seitenverhältnis bleibt erhalten
+pub const THUMBNAIL_SIZE: [u32; 2] = [320, 180];
+/// Abstand der Aufnahmen während des Spiels
+const CAPTURE_INTERVAL_SECS: f32 = 30.0;
+/// Erste Aufnahme kurz nach dem Fortsetzen, wenn die Welt sichtbar ist
+const FIRST_CAPTURE_SECS: f32 = 1.0;
+
+/// Plugin für Vorschaubilder der Spielstände.
+///
+/// Nach jedem [`SaveWritten`] wird ein verkleinerter Screenshot des Hauptfensters
+/// neben `meta.toml` abgelegt. Gespeichert wird aber auch aus dem Pausenmenü und beim
+/// Verlassen der Session, wenn nur noch Menüs zu sehen sind. Daher wird die Welt
+/// während des Spiels regelmäßig aufgenommen und in diesen Fällen die letzte
+/// Aufnahme verwendet. Ohne Fenster (headless) wird nichts aufgenommen; die
+/// Spielstandliste zeigt dann einen Platzhalter.
+pub struct SaveThumbnailPlugin;
+
+impl Plugin for SaveThumbnailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldCapture>()
+            .add_observer(on_save_written)
+            .add_systems(OnEnter(AppScope::Session), reset_world_capture)
+            .add_systems(OnEnter(SessionState::Active), schedule_first_capture)
+            .add_systems(Update, capture_world.run_if(in_state(SessionState::Active)));
+    }
+}
+
+/// Letzte Aufnahme der Welt ohne Menüs, bereits verkleinert
+#[derive(Resource)]
+struct WorldCapture {
+    latest: Option<RgbImage>,
+    next: Timer,
+}
+
+impl Default for WorldCapture {
+    fn default() -> Self {
+        Self {
+            latest: None,
+            next: Timer::from_seconds(FIRST_CAPTURE_SECS, TimerMode::Once),
+        }
+    }
+}
+
+pub fn thumbnail_path(save_dir: &Path) -> PathBuf {
+    save_dir.join(THUMBNAIL_FILE)
+}
+
+fn reset_world_capture(mut capture: ResMut<WorldCapture>) {
+    *capture = WorldCapture::default();
+}
+
+fn schedule_first_capture(mut capture: ResMut<WorldCapture>) {
+    capture.next = Timer::from_seconds(FIRST_CAPTURE_SECS, TimerMode::Once);
+}
+
+fn capture_world(
+    mut commands: Commands,
+    mut capture: ResMut<WorldCapture>,
+    time: Res<Time<Real>>,
+    window: Option<Single<(), With<PrimaryWindow>>>,
+) {
+    if window.is_none() || !capture.next.tick(time.delta()).just_finished() {
+        return;
+    }
+    capture.next = Timer::from_seconds(CAPTURE_INTERVAL_SECS, TimerMode::Once);
+    commands.spawn(Screenshot::primary_window()).observe(
+        |captured: On<ScreenshotCaptured>, mut capture: ResMut<WorldCapture>| match make_thumbnail(
+            &captured.image,
+        ) {
+            Ok(thumbnail) => capture.latest = Some(thumbnail),
+            Err(err) => warn!("Could not capture world for thumbnails: {err:#}"),
+        },
+    );
+}
+
+fn on_save_written(
+    event: On<SaveWritten>,
+    mut commands: Commands,
+    capture: Res<WorldCapture>,
+    session: Option<Res<State<SessionState>>>,
+    window: Option<Single<(), With<PrimaryWindow>>>,
+) {
+    if window.is_none() {
+        return;
+    }
+
+    let path = thumbnail_path(&event.dir);
+    // Im Spiel (Autosave) zeigt das Fenster gerade die Welt
+    if session.is_some_and(|session| *session.get() == SessionState::Active) {
+        commands.spawn(Screenshot::primary_window()).observe(
+            move |captured: On<ScreenshotCaptured>| {
+                let result = make_thumbnail(&captured.image)
+                    .and_then(|thumbnail| write_thumbnail(&thumbnail, &path));
+                log_thumbnail_result(result, &path);
+            },
+        );
+    } else if let Some(thumbnail) = &capture.latest {
+        log_thumbnail_result(write_thumbnail(thumbnail, &path), &path);
+    }
+}
+
+fn log_thumbnail_result(result: anyhow::Result<()>, path: &Path) {
+    match result {
+        Ok(()) => debug!("Saved thumbnail {}", path.display()),
+        Err(err) => warn!("Could not save thumbnail: {err:#}"),
+    }
+}
+
+fn make_thumbnail(screenshot: &Image) -> anyhow::Result<RgbImage> {
+    let image = screenshot
+        .clone()
+        .try_into_dynamic()
+        .context("converting screenshot")?;
+    // Der Alpha-Kanal enthält bei HDR Helligkeitswerte, daher nur RGB speichern
+    Ok(image
+        .thumbnail(THUMBNAIL_SIZE[0], THUMBNAIL_SIZE[1])
+        .to_rgb8())
+}
+
+fn write_thumbnail(thumbnail: &RgbImage, path: &Path) -> anyhow::Result<()> {
+    thumbnail
+        .save_with_format(path, image::ImageFormat::Png)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+/// Lädt das Vorschaubild eines Spielstands als egui-Textur; `None`, wenn keins existiert
+pub fn load_thumbnail(ctx: &egui::Context, save_dir: &Path) -> Option<egui::TextureHandle> {
+    let path = thumbnail_path(save_dir);
+    let bytes = fs::read(&path).ok()?;
+    let image = match image::load_from_memory_with_format(&bytes, image::ImageFormat::Png) {
+        Ok(image) => image.to_rgba8(),
+        Err(err) => {
+            warn!("Invalid thumbnail {}: {err}", path.display());
+            return None;
+        }
+    };
+
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    Some(ctx.load_texture(
+        path.display().to_string(),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        bevy::{
+            asset::RenderAssetUsages,
+            render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+        },
+    };
+
+    #[test]
+    fn thumbnails_are_downscaled() {
+        let screenshot = Image::new_fill(
+            Extent3d {
+                width: 1280,
+                height: 800,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[200, 100, 50, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        let path = std::env::temp_dir().join(format!("fos-thumbnail-{}.png", std::process::id()));
+
+        write_thumbnail(&make_thumbnail(&screenshot).unwrap(), &path).unwrap();
+        let thumbnail = image::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!((thumbnail.width(), thumbnail.height()), (288, 180));
+    }
+}
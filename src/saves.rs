@@ -0,0 +1,562 @@
+use {
+    crate::{
+        config::VERSION,
+        paths,
+        world_config::{WorldConfig, WorldConfigForm},
+    },
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::menu::{multiplayer::SetNewHostGame, singleplayer::SetSingleplayerNewGame},
+        states::{app::AppScope, session::SessionState},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        fmt, fs,
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Dateiname der Metadaten im Ordner eines Spielstands
+pub const META_FILE: &str = "meta.toml";
+/// Auswählbare Autosave-Intervalle in Minuten, 0 = aus
+pub const AUTOSAVE_INTERVALS: [u32; 5] = [0, 5, 10, 15, 30];
+/// Maximale Länge eines Spielstandnamens
+pub const SAVE_NAME_MAX_LENGTH: usize = 48;
+
+/// Plugin für Spielstand-Metadaten, Autosave und den "Configure Save"-Schritt.
+///
+/// Die Weltdaten selbst schreibt der Server; der Client verwaltet je Spielstand
+/// einen Ordner unter [`paths::saves_dir`] mit `meta.toml` (Name, Seed, Spielzeit, …).
+pub struct SavesPlugin;
+
+impl Plugin for SavesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveConfigForm::new(paths::saves_dir()))
+            .add_observer(on_singleplayer_confirm)
+            .add_observer(on_host_confirm)
+            .add_observer(on_write_save)
+            .add_systems(
+                Update,
+                track_playtime
+                    .run_if(resource_exists::<ActiveSave>)
+                    .run_if(in_state(SessionState::Active)),
+            )
+            .add_systems(OnExit(AppScope::Session), write_save_on_exit);
+    }
+}
+
+/// Metadaten eines Spielstands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveMeta {
+    pub name: String,
+    pub world: WorldConfig,
+    /// Unix-Zeit in Sekunden
+    pub created: u64,
+    /// Unix-Zeit in Sekunden
+    pub last_played: u64,
+    pub playtime_secs: u64,
+    pub game_version: String,
+    /// Autosave-Intervall in Minuten, 0 = aus
+    pub autosave_minutes: u32,
+}
+
+impl SaveMeta {
+    pub fn new(name: String, world: WorldConfig, autosave_minutes: u32) -> Self {
+        let now = unix_now();
+        Self {
+            name,
+            world,
+            created: now,
+            last_played: now,
+            playtime_secs: 0,
+            game_version: VERSION.to_string(),
+            autosave_minutes,
+        }
+    }
+
+    /// Wurde der Spielstand mit dieser Client-Version erstellt bzw. zuletzt gespeichert?
+    pub fn is_current_version(&self) -> bool {
+        self.game_version == VERSION
+    }
+
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(META_FILE);
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        let path = dir.join(META_FILE);
+        let content = toml::to_string_pretty(self).context("serializing save metadata")?;
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Ein Spielstand auf der Platte
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSlot {
+    pub dir: PathBuf,
+    pub meta: SaveMeta,
+}
+
+/// Alle Spielstände mit lesbaren Metadaten; kaputte Ordner werden übersprungen
+pub fn list_saves(saves_dir: &Path) -> Vec<SaveSlot> {
+    let Ok(entries) = fs::read_dir(saves_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !is_hidden(path))
+        .filter_map(|dir| match SaveMeta::load(&dir) {
+            Ok(meta) => Some(SaveSlot { dir, meta }),
+            Err(err) => {
+                warn!("Skipping save {}: {err:#}", dir.display());
+                None
+            }
+        })
+        .collect()
+}
+
+/// Zwischenordner von [`replace_dir`] beginnen mit '.', Spielstandnamen nie
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Ersetzt `dir` durch den fertig vorbereiteten Ordner `staged`. Ein vorhandener
+/// Ordner wird erst beiseitegelegt und nur gelöscht, wenn der neue an seinem
+/// Platz liegt; schlägt das Umbenennen fehl, wird er zurückgeholt.
+fn replace_dir(staged: &Path, dir: &Path) -> std::io::Result<()> {
+    let Some(name) = dir.file_name().and_then(|name| name.to_str()) else {
+        return Err(std::io::ErrorKind::InvalidInput.into());
+    };
+    let old = dir.with_file_name(format!(".{name}.old"));
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+    let had_old = dir.exists();
+    if had_old {
+        fs::rename(dir, &old)?;
+    }
+    if let Err(err) = fs::rename(staged, dir) {
+        if had_old && let Err(restore) = fs::rename(&old, dir) {
+            error!("Could not restore save {}: {restore}", dir.display());
+        }
+        return Err(err);
+    }
+    if had_old && let Err(err) = fs::remove_dir_all(&old) {
+        warn!("Could not remove old save {}: {err}", old.display());
+    }
+    Ok(())
+}
+
+/// Grund, warum ein Spielstandname nicht verwendet werden kann
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveNameError {
+    Empty,
+    TooLong,
+    InvalidCharacters,
+    Reserved,
+}
+
+impl fmt::Display for SaveNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveNameError::Empty => write!(f, "Enter a name for the save"),
+            SaveNameError::TooLong => {
+                write!(f, "Name must be at most {SAVE_NAME_MAX_LENGTH} characters")
+            }
+            SaveNameError::InvalidCharacters => write!(
+                f,
+                "Name may only contain letters, digits, spaces and - _ ( ) and must not start with '.'"
+            ),
+            SaveNameError::Reserved => write!(f, "This name is reserved by the operating system"),
+        }
+    }
+}
+
+/// Prüft, ob der Name auf allen Plattformen als Ordnername taugt, und gibt ihn getrimmt zurück
+pub fn validate_save_name(name: &str) -> Result<&str, SaveNameError> {
+    const RESERVED: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(SaveNameError::Empty);
+    }
+    if name.chars().count() > SAVE_NAME_MAX_LENGTH {
+        return Err(SaveNameError::TooLong);
+    }
+    if name.starts_with('.')
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '(' | ')'))
+    {
+        return Err(SaveNameError::InvalidCharacters);
+    }
+
+    let upper = name.to_ascii_uppercase();
+    let is_device = |prefix: &str| {
+        upper
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.len() == 1 && rest.as_bytes()[0].is_ascii_digit())
+    };
+    if RESERVED.contains(&upper.as_str()) || is_device("COM") || is_device("LPT") {
+        return Err(SaveNameError::Reserved);
+    }
+    Ok(name)
+}
+
+/// Spielstand der laufenden Session
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveSave {
+    pub dir: PathBuf,
+    pub meta: SaveMeta,
+    /// Spielzeit seit dem letzten Schreiben
+    unsaved_playtime: Duration,
+    autosave_timer: Option<Timer>,
+}
+
+impl ActiveSave {
+    pub fn new(dir: PathBuf, meta: SaveMeta) -> Self {
+        let autosave_timer = (meta.autosave_minutes > 0).then(|| {
+            Timer::from_seconds(meta.autosave_minutes as f32 * 60.0, TimerMode::Repeating)
+        });
+        Self {
+            dir,
+            meta,
+            unsaved_playtime: Duration::ZERO,
+            autosave_timer,
+        }
+    }
+}
+
+/// Fordert das Schreiben des aktiven Spielstands an (Autosave, Session-Ende, manuell)
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WriteSave;
+
+/// Wurde ausgelöst, nachdem ein Spielstand geschrieben wurde
+#[derive(Event, Debug, Clone)]
+pub struct SaveWritten {
+    pub dir: PathBuf,
+}
+
+fn track_playtime(mut commands: Commands, time: Res<Time<Real>>, mut save: ResMut<ActiveSave>) {
+    save.unsaved_playtime += time.delta();
+    let autosave = save
+        .autosave_timer
+        .as_mut()
+        .is_some_and(|timer| timer.tick(time.delta()).just_finished());
+    if autosave {
+        commands.trigger(WriteSave);
+    }
+}
+
+/// Schreibt den Spielstand ein letztes Mal und beendet ihn mit der Session
+fn write_save_on_exit(mut commands: Commands, save: Option<Res<ActiveSave>>) {
+    if save.is_some() {
+        commands.trigger(WriteSave);
+        commands.remove_resource::<ActiveSave>();
+    }
+}
+
+fn on_write_save(_: On<WriteSave>, mut commands: Commands, save: Option<ResMut<ActiveSave>>) {
+    let Some(mut save) = save else {
+        return;
+    };
+
+    let playtime = std::mem::take(&mut save.unsaved_playtime);
+    save.meta.playtime_secs += playtime.as_secs();
+    save.meta.last_played = unix_now();
+
+    match save.meta.save(&save.dir) {
+        Ok(()) => commands.trigger(SaveWritten {
+            dir: save.dir.clone(),
+        }),
+        Err(err) => error!("Could not write save metadata: {err:#}"),
+    }
+}
+
+/// Formularzustand von "Configure Save"; gemeinsam für Singleplayer und Host
+#[derive(Resource, Debug)]
+pub struct SaveConfigForm {
+    pub name: String,
+    pub autosave_minutes: u32,
+    saves_dir: PathBuf,
+    existing: Vec<String>,
+    confirm_overwrite: bool,
+}
+
+impl SaveConfigForm {
+    pub fn new(saves_dir: PathBuf) -> Self {
+        let mut form = Self {
+            name: String::new(),
+            autosave_minutes: 10,
+            saves_dir,
+            existing: Vec::new(),
+            confirm_overwrite: false,
+        };
+        form.refresh();
+        form
+    }
+
+    /// Liest die vorhandenen Spielstandordner neu ein
+    pub fn refresh(&mut self) {
+        self.existing = fs::read_dir(&self.saves_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().is_dir() && !is_hidden(&entry.path()))
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    /// Ordner des Spielstands. Gibt es ihn schon in anderer Schreibweise, wird dessen
+    /// Name verwendet, damit er auf allen Dateisystemen ersetzt wird.
+    pub fn slot_dir(&self) -> Option<PathBuf> {
+        validate_save_name(&self.name).ok().map(|name| {
+            self.saves_dir
+                .join(self.existing_name(name).unwrap_or(name))
+        })
+    }
+
+    /// Existiert bereits ein Spielstand mit diesem Namen (ohne Groß-/Kleinschreibung)?
+    pub fn overwrites_existing(&self) -> bool {
+        validate_save_name(&self.name).is_ok_and(|name| self.existing_name(name).is_some())
+    }
+
+    fn existing_name(&self, name: &str) -> Option<&str> {
+        self.existing
+            .iter()
+            .find(|existing| existing.eq_ignore_ascii_case(name))
+            .map(String::as_str)
+    }
+}
+
+fn on_singleplayer_confirm(
+    event: On<SetSingleplayerNewGame>,
+    commands: Commands,
+    form: Res<SaveConfigForm>,
+    world: Res<WorldConfigForm>,
+) {
+    if matches!(*event, SetSingleplayerNewGame::Confirm) {
+        create_save(commands, &form, &world);
+    }
+}
+
+fn on_host_confirm(
+    event: On<SetNewHostGame>,
+    commands: Commands,
+    form: Res<SaveConfigForm>,
+    world: Res<WorldConfigForm>,
+) {
+    if matches!(*event, SetNewHostGame::Confirm) {
+        create_save(commands, &form, &world);
+    }
+}
+
+/// Legt den Spielstand-Ordner an und macht ihn zum aktiven Spielstand
+fn create_save(mut commands: Commands, form: &SaveConfigForm, world: &WorldConfigForm) {
+    // Ein Fehler unten darf nicht den Spielstand der letzten Session weiterschreiben
+    commands.remove_resource::<ActiveSave>();
+    let (Ok(name), Some(dir), Some(world)) = (
+        validate_save_name(&form.name),
+        form.slot_dir(),
+        world.to_config(),
+    ) else {
+        warn!("Save configuration is invalid, starting without save metadata");
+        return;
+    };
+
+    let staged = dir.with_file_name(format!(".{name}.new"));
+    if staged.exists()
+        && let Err(err) = fs::remove_dir_all(&staged)
+    {
+        error!("Could not clear {}: {err}", staged.display());
+        return;
+    }
+    let meta = SaveMeta::new(name.to_string(), world, form.autosave_minutes);
+    if let Err(err) = meta.save(&staged) {
+        error!("Could not create save: {err:#}");
+        return;
+    }
+    if let Err(err) = replace_dir(&staged, &dir) {
+        error!("Could not overwrite save {}: {err}", dir.display());
+        return;
+    }
+    info!("Created save {}", dir.display());
+    commands.insert_resource(ActiveSave::new(dir, meta));
+}
+
+// --- UI ---
+
+/// Rendert das Formular. Gibt `true` zurück, wenn die Eingaben gültig sind.
+pub fn render_save_config_form(ui: &mut egui::Ui, form: &mut SaveConfigForm) -> bool {
+    egui::Grid::new("save_config_form")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Save name");
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut form.name)
+                        .char_limit(SAVE_NAME_MAX_LENGTH)
+                        .hint_text("My World"),
+                )
+                .gained_focus()
+            {
+                form.refresh();
+            }
+            ui.end_row();
+
+            ui.label("Autosave");
+            egui::ComboBox::from_id_salt("save_autosave")
+                .selected_text(autosave_label(form.autosave_minutes))
+                .show_ui(ui, |ui| {
+                    for minutes in AUTOSAVE_INTERVALS {
+                        ui.selectable_value(
+                            &mut form.autosave_minutes,
+                            minutes,
+                            autosave_label(minutes),
+                        );
+                    }
+                });
+            ui.end_row();
+        });
+
+    match validate_save_name(&form.name) {
+        Ok(_) => {
+            if form.overwrites_existing() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 180, 80),
+                    "A save with this name already exists and will be overwritten",
+                );
+            }
+            true
+        }
+        Err(err) => {
+            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), err.to_string());
+            false
+        }
+    }
+}
+
+fn autosave_label(minutes: u32) -> String {
+    match minutes {
+        0 => "Off".to_string(),
+        minutes => format!("Every {minutes} min"),
+    }
+}
+
+/// Bestätigt den Start aus dem letzten Schritt (`requested`). Fragt vor dem Überschreiben
+/// eines vorhandenen Spielstands nach; gibt `true` zurück, sobald gestartet werden soll.
+pub fn confirm_start(ui: &mut egui::Ui, form: &mut SaveConfigForm, requested: bool) -> bool {
+    let mut start = false;
+    if requested {
+        form.refresh();
+        if form.overwrites_existing() {
+            form.confirm_overwrite = true;
+        } else {
+            start = true;
+        }
+    }
+
+    if form.confirm_overwrite {
+        egui::Modal::new(egui::Id::new("save_overwrite")).show(ui.ctx(), |ui| {
+            ui.heading("Overwrite save?");
+            ui.label(format!(
+                "\"{}\" already exists. Its world will be replaced.",
+                form.name.trim()
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("Overwrite").clicked() {
+                    form.confirm_overwrite = false;
+                    start = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    form.confirm_overwrite = false;
+                }
+            });
+        });
+    }
+
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_names_must_be_filesystem_safe() {
+        assert_eq!(validate_save_name("  My World (2) "), Ok("My World (2)"));
+        assert_eq!(validate_save_name("   "), Err(SaveNameError::Empty));
+        assert_eq!(
+            validate_save_name("../evil"),
+            Err(SaveNameError::InvalidCharacters)
+        );
+        assert_eq!(
+            validate_save_name("a/b"),
+            Err(SaveNameError::InvalidCharacters)
+        );
+        assert_eq!(validate_save_name("con"), Err(SaveNameError::Reserved));
+        assert_eq!(validate_save_name("LPT1"), Err(SaveNameError::Reserved));
+        assert_eq!(validate_save_name("Console"), Ok("Console"));
+    }
+
+    #[test]
+    fn saves_are_listed_with_metadata() {
+        let dir = std::env::temp_dir().join(format!("fos-saves-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let meta = SaveMeta::new("Alpha".to_string(), WorldConfig::default(), 5);
+        meta.save(&dir.join("Alpha")).unwrap();
+        fs::create_dir_all(dir.join("broken")).unwrap();
+
+        let saves = list_saves(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(saves.len(), 1);
+        assert_eq!(saves[0].meta, meta);
+    }
+
+    #[test]
+    fn overwriting_keeps_the_existing_folder_name() {
+        let dir = std::env::temp_dir().join(format!("fos-overwrite-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let old = SaveMeta::new("my world".to_string(), WorldConfig::default(), 5);
+        old.save(&dir.join("my world")).unwrap();
+        fs::write(dir.join("my world").join("world.bin"), "old").unwrap();
+
+        let mut form = SaveConfigForm::new(dir.clone());
+        form.name = "My World".to_string();
+        let slot = form.slot_dir().unwrap();
+        let new = SaveMeta::new("My World".to_string(), WorldConfig::default(), 0);
+        let staged = dir.join(".My World.new");
+        new.save(&staged).unwrap();
+        replace_dir(&staged, &slot).unwrap();
+
+        let saves = list_saves(&dir);
+        let old_world_kept = dir.join("my world").join("world.bin").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(form.overwrites_existing());
+        assert_eq!(slot, dir.join("my world"));
+        assert_eq!(saves.len(), 1);
+        assert_eq!(saves[0].meta, new);
+        assert!(!old_world_kept);
+    }
+}
@@ -0,0 +1,481 @@
+use {
+    crate::{
+        address::JoinAddress,
+        paths,
+        save_browser::format_timestamp,
+        saves::unix_now,
+        server_query::{self, ServerQueries},
+    },
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::events::menu::multiplayer::SetJoinGame,
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Anzahl der gemerkten zuletzt besuchten Server
+pub const RECENT_LIMIT: usize = 10;
+
+/// Plugin für Favoriten und zuletzt besuchte Server in "Join Game".
+///
+/// Beide Listen liegen in `servers.toml` im Konfigurationsverzeichnis; jeder
+/// `SetJoinGame::Confirm` schiebt die Zieladresse an den Anfang der Recent-Liste.
+pub struct ServerListPlugin;
+
+impl Plugin for ServerListPlugin {
+    fn build(&self, app: &mut App) {
+        let form = ServerListForm::new(ServerList::load_or_default(&server_list_path()));
+        app.insert_resource(form).add_observer(on_join_confirm);
+    }
+}
+
+fn server_list_path() -> PathBuf {
+    paths::config_dir().join("servers.toml")
+}
+
+/// Ein gespeicherter Server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerEntry {
+    pub name: String,
+    /// Adresse wie im Eingabefeld, z.B. `192.168.0.10:8080`
+    pub address: String,
+    /// Unix-Zeit in Sekunden
+    pub last_joined: Option<u64>,
+    pub notes: String,
+}
+
+impl Default for ServerEntry {
+    fn default() -> Self {
+        Self {
+            name: "New Server".to_string(),
+            address: String::new(),
+            last_joined: None,
+            notes: String::new(),
+        }
+    }
+}
+
+/// Favoriten und zuletzt besuchte Server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerList {
+    pub favorites: Vec<ServerEntry>,
+    pub recent: Vec<ServerEntry>,
+}
+
+impl ServerList {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn load_or_default(path: &Path) -> Self {
+        match Self::load(path) {
+            Ok(list) => list,
+            Err(err) => {
+                if path.exists() {
+                    warn!("Invalid server list: {err:#}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("serializing server list")?;
+        fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn is_favorite(&self, address: &str) -> bool {
+        self.favorites
+            .iter()
+            .any(|entry| entry.address.eq_ignore_ascii_case(address))
+    }
+
+    /// Merkt einen Beitritt: aktualisiert den Favoriten und schiebt die Adresse an den
+    /// Anfang der Recent-Liste
+    pub fn record_join(&mut self, address: &str, now: u64) {
+        let address = address.trim();
+        if address.is_empty() {
+            return;
+        }
+
+        let mut name = address.to_string();
+        if let Some(favorite) = self
+            .favorites
+            .iter_mut()
+            .find(|entry| entry.address.eq_ignore_ascii_case(address))
+        {
+            favorite.last_joined = Some(now);
+            name = favorite.name.clone();
+        }
+
+        let previous = self
+            .recent
+            .iter()
+            .position(|entry| entry.address.eq_ignore_ascii_case(address))
+            .map(|index| self.recent.remove(index));
+        self.recent.insert(
+            0,
+            ServerEntry {
+                name,
+                address: address.to_string(),
+                last_joined: Some(now),
+                notes: previous.map(|entry| entry.notes).unwrap_or_default(),
+            },
+        );
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Übernimmt Favoriten aus einer anderen Liste; bekannte Adressen werden übersprungen.
+    /// Gibt die Anzahl neuer Einträge zurück.
+    pub fn merge_favorites(&mut self, other: ServerList) -> usize {
+        let mut added = 0;
+        for entry in other.favorites {
+            if !entry.address.trim().is_empty() && !self.is_favorite(&entry.address) {
+                self.favorites.push(entry);
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+/// Zustand der Server-Listen in "Join Game"
+#[derive(Resource, Debug)]
+pub struct ServerListForm {
+    pub list: ServerList,
+    /// Index und Arbeitskopie des gerade bearbeiteten Favoriten
+    editing: Option<(usize, ServerEntry)>,
+    /// Datei für Import/Export
+    pub transfer_path: String,
+    status: Option<String>,
+}
+
+impl ServerListForm {
+    pub fn new(list: ServerList) -> Self {
+        Self {
+            list,
+            editing: None,
+            transfer_path: paths::config_dir()
+                .join("servers_export.toml")
+                .display()
+                .to_string(),
+            status: None,
+        }
+    }
+
+    fn persist(&mut self) {
+        if let Err(err) = self.list.save(&server_list_path()) {
+            self.status = Some(format!("Could not save server list: {err:#}"));
+        }
+    }
+
+    fn export(&mut self) {
+        let path = PathBuf::from(self.transfer_path.trim());
+        let favorites = ServerList {
+            favorites: self.list.favorites.clone(),
+            recent: Vec::new(),
+        };
+        self.status = Some(match favorites.save(&path) {
+            Ok(()) => format!(
+                "Exported {} favorites to {}",
+                favorites.favorites.len(),
+                path.display()
+            ),
+            Err(err) => format!("Export failed: {err:#}"),
+        });
+    }
+
+    fn import(&mut self) {
+        let path = PathBuf::from(self.transfer_path.trim());
+        match ServerList::load(&path) {
+            Ok(other) => {
+                let added = self.list.merge_favorites(other);
+                self.status = Some(format!("Imported {added} favorites"));
+                self.persist();
+            }
+            Err(err) => self.status = Some(format!("Import failed: {err:#}")),
+        }
+    }
+}
+
+fn on_join_confirm(
+    event: On<SetJoinGame>,
+    mut form: ResMut<ServerListForm>,
+    join: Res<JoinAddress>,
+) {
+    if !matches!(*event, SetJoinGame::Confirm) || join.resolved().is_none() {
+        return;
+    }
+    // Die Eingabe statt der aufgelösten IP merken, damit Hostnamen erhalten bleiben
+    form.list.record_join(join.input.trim(), unix_now());
+    form.persist();
+}
+
+// --- UI ---
+
+/// Von der Server-Liste angeforderte Aktion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerListAction {
+    /// Adresse ins Eingabefeld übernehmen
+    Select(String),
+    /// Adresse übernehmen und sofort beitreten
+    Join(String),
+}
+
+/// Rendert Favoriten, Recent-Liste und Import/Export. `current_address` ist die
+/// aktuelle Eingabe, die als Favorit gespeichert werden kann.
+pub fn render_server_lists(
+    ui: &mut egui::Ui,
+    form: &mut ServerListForm,
+    queries: &ServerQueries,
+    current_address: Option<&str>,
+) -> Option<ServerListAction> {
+    let mut action = None;
+    let mut changed = false;
+
+    ui.heading("Favorites");
+    if form.list.favorites.is_empty() {
+        ui.weak("No favorites yet.");
+    }
+    let mut remove = None;
+    for (index, entry) in form.list.favorites.iter().enumerate() {
+        if form
+            .editing
+            .as_ref()
+            .is_some_and(|(editing, _)| *editing == index)
+        {
+            continue;
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Join").clicked() {
+                action = Some(ServerListAction::Join(entry.address.clone()));
+            }
+            let label = ui
+                .selectable_label(
+                    false,
+                    format!(
+                        "{} ({}) · {}",
+                        entry.name,
+                        entry.address,
+                        server_query::status_text(queries, &entry.address)
+                    ),
+                )
+                .on_hover_text(entry_details(entry));
+            if label.clicked() {
+                action = Some(ServerListAction::Select(entry.address.clone()));
+            }
+            if ui.small_button("✏").on_hover_text("Edit").clicked() {
+                form.editing = Some((index, entry.clone()));
+            }
+            if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                remove = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        form.list.favorites.remove(index);
+        form.editing = None;
+        changed = true;
+    }
+    changed |= render_favorite_editor(ui, form);
+
+    if let Some(address) = current_address.map(str::trim).filter(|a| !a.is_empty())
+        && !form.list.is_favorite(address)
+        && ui.button(format!("☆ Add {address} to favorites")).clicked()
+    {
+        form.list.favorites.push(ServerEntry {
+            name: address.to_string(),
+            address: address.to_string(),
+            ..default()
+        });
+        changed = true;
+    }
+
+    ui.separator();
+    ui.heading("Recent");
+    if form.list.recent.is_empty() {
+        ui.weak("No servers joined yet.");
+    }
+    let mut favorite = None;
+    for entry in &form.list.recent {
+        ui.horizontal(|ui| {
+            if ui.button("Join").clicked() {
+                action = Some(ServerListAction::Join(entry.address.clone()));
+            }
+            let label = ui
+                .selectable_label(
+                    false,
+                    format!(
+                        "{} ({}) · {}",
+                        entry.name,
+                        entry.address,
+                        server_query::status_text(queries, &entry.address)
+                    ),
+                )
+                .on_hover_text(entry_details(entry));
+            if label.clicked() {
+                action = Some(ServerListAction::Select(entry.address.clone()));
+            }
+            if !form.list.is_favorite(&entry.address)
+                && ui
+                    .small_button("☆")
+                    .on_hover_text("Add to favorites")
+                    .clicked()
+            {
+                favorite = Some(entry.clone());
+            }
+        });
+    }
+    if let Some(entry) = favorite {
+        form.list.favorites.push(entry);
+        changed = true;
+    }
+    if !form.list.recent.is_empty() && ui.small_button("Clear recent").clicked() {
+        form.list.recent.clear();
+        changed = true;
+    }
+
+    if changed {
+        form.persist();
+    }
+
+    egui::CollapsingHeader::new("Import / Export favorites").show(ui, |ui| {
+        ui.add(egui::TextEdit::singleline(&mut form.transfer_path).hint_text("File path"));
+        ui.horizontal(|ui| {
+            if ui.button("Export").clicked() {
+                form.export();
+            }
+            if ui.button("Import").clicked() {
+                form.import();
+            }
+        });
+    });
+    if let Some(status) = &form.status {
+        ui.label(status);
+    }
+
+    action
+}
+
+fn entry_details(entry: &ServerEntry) -> String {
+    let last_joined = entry.last_joined.map_or_else(
+        || "never".to_string(),
+        |time| format_timestamp(time) + " UTC",
+    );
+    if entry.notes.is_empty() {
+        format!("Last joined: {last_joined}")
+    } else {
+        format!("Last joined: {last_joined}\n{}", entry.notes)
+    }
+}
+
+/// Bearbeitungszeile des gewählten Favoriten. Gibt `true` zurück, wenn gespeichert wurde.
+fn render_favorite_editor(ui: &mut egui::Ui, form: &mut ServerListForm) -> bool {
+    let Some((index, entry)) = form.editing.as_mut() else {
+        return false;
+    };
+
+    let mut save = false;
+    let mut close = false;
+    egui::Grid::new("favorite_editor")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut entry.name);
+            ui.end_row();
+
+            ui.label("Address");
+            ui.text_edit_singleline(&mut entry.address);
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut entry.notes);
+            ui.end_row();
+        });
+    ui.horizontal(|ui| {
+        let valid = !entry.name.trim().is_empty() && !entry.address.trim().is_empty();
+        if ui.add_enabled(valid, egui::Button::new("Save")).clicked() {
+            save = true;
+        }
+        if ui.button("Cancel").clicked() {
+            close = true;
+        }
+    });
+
+    if save {
+        let index = *index;
+        let mut entry = entry.clone();
+        entry.name = entry.name.trim().to_string();
+        entry.address = entry.address.trim().to_string();
+        if let Some(favorite) = form.list.favorites.get_mut(index) {
+            *favorite = entry;
+        }
+        form.editing = None;
+        return true;
+    }
+    if close {
+        form.editing = None;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn favorite(name: &str, address: &str) -> ServerEntry {
+        ServerEntry {
+            name: name.to_string(),
+            address: address.to_string(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn joins_are_recorded_most_recent_first() {
+        let mut list = ServerList {
+            favorites: vec![favorite("Home", "10.0.0.2:8080")],
+            recent: Vec::new(),
+        };
+        list.record_join("10.0.0.2:8080", 10);
+        list.record_join("10.0.0.3:8080", 20);
+        list.record_join(" 10.0.0.2:8080 ", 30);
+
+        let recent: Vec<_> = list.recent.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(recent, ["Home", "10.0.0.3:8080"]);
+        assert_eq!(list.favorites[0].last_joined, Some(30));
+
+        for i in 0..RECENT_LIMIT as u64 + 5 {
+            list.record_join(&format!("10.0.1.{i}:8080"), 100 + i);
+        }
+        assert_eq!(list.recent.len(), RECENT_LIMIT);
+    }
+
+    #[test]
+    fn import_skips_known_addresses() {
+        let mut list = ServerList {
+            favorites: vec![favorite("Home", "10.0.0.2:8080")],
+            recent: Vec::new(),
+        };
+        let imported = ServerList {
+            favorites: vec![
+                favorite("Duplicate", "10.0.0.2:8080"),
+                favorite("Friend", "friend.example:8080"),
+            ],
+            recent: Vec::new(),
+        };
+        assert_eq!(list.merge_favorites(imported), 1);
+        assert_eq!(list.favorites[1].name, "Friend");
+    }
+}
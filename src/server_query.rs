@@ -0,0 +1,573 @@
+use {
+    crate::{
+        address::{AddressError, ServerAddress},
+        compatibility::{self, Compatibility},
+        config::{PROTOCOL_HASH, VERSION},
+        host_config::{HOST_PORT, HostConfig},
+        server_list::{ServerListAction, ServerListForm},
+        settings::UserSettings,
+    },
+    bevy::{
+        prelude::*,
+        tasks::{Task, futures::check_ready},
+    },
+    bevy_egui::egui,
+    chicken::{
+        network::client::DiscoveredServers,
+        states::states::{
+            app::AppScope, menu::multiplayer::MultiplayerMenuScreen, session::ServerVisibility,
+        },
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        cmp::Ordering,
+        collections::HashMap,
+        io::ErrorKind,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+        time::{Duration, Instant},
+    },
+};
+
+/// Der Info-Dienst lauscht auf UDP direkt neben dem Spielport
+pub const QUERY_PORT_OFFSET: u16 = 1;
+/// Abstand der Abfragen, solange "Join Game" offen ist
+pub const QUERY_INTERVAL: Duration = Duration::from_secs(5);
+/// Ohne Antwort nach dieser Zeit gilt ein Server als stumm
+pub const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+const REQUEST_MAGIC: &[u8; 5] = b"FOSQ?";
+const RESPONSE_MAGIC: &[u8; 5] = b"FOSQ!";
+/// Obergrenze jeder Antwort; Anfragen werden auf diese Größe aufgefüllt
+const MAX_DATAGRAM: usize = 1200;
+
+/// Plugin für Server-Infos und Ping in "Join Game".
+///
+/// Der Server dieser Instanz beantwortet Anfragen auf `Port + 1` mit [`ServerInfo`],
+/// solange er öffentlich ist; private Server verraten weder Name noch MOTD. Der
+/// Join-Bildschirm fragt gefundene Server und Favoriten regelmäßig ab und misst
+/// dabei die Round-Trip-Zeit. Server ohne Info-Dienst (z.B. dedizierte) bleiben
+/// erreichbar, werden aber ohne Infos als "no answer" angezeigt. Spielerzahl,
+/// Spielerlimit und Passwortschutz fehlen, weil chicken sie nicht preisgibt.
+pub struct ServerQueryPlugin;
+
+impl Plugin for ServerQueryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServerQueries>()
+            .add_systems(OnEnter(ServerVisibility::Public), start_query_responder)
+            .add_systems(OnExit(ServerVisibility::Public), stop_query_responder)
+            .add_systems(OnExit(AppScope::Session), stop_query_responder)
+            .add_systems(
+                Update,
+                (
+                    answer_queries.run_if(resource_exists::<QueryResponder>),
+                    poll_server_queries.run_if(in_state(MultiplayerMenuScreen::JoinGame)),
+                ),
+            );
+    }
+}
+
+/// Öffentliche Infos eines Servers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub name: String,
+    pub motd: String,
+    pub version: String,
+    /// Fehlt bei Servern, die älter als der Kompatibilitäts-Check sind
+    #[serde(default)]
+    pub protocol: String,
+}
+
+impl ServerInfo {
+    pub fn compatibility(&self) -> Compatibility {
+        compatibility::check(&self.version, &self.protocol)
+    }
+}
+
+/// Die Anfrage enthält den Protokoll-Hash des fragenden Clients
+/// Anfrage, mit Nullbytes auf [`MAX_DATAGRAM`] aufgefüllt. Der Server antwortet
+/// nie mit mehr Bytes, als er empfangen hat, und taugt so nicht als Verstärker.
+fn encode_request(nonce: u64) -> Vec<u8> {
+    let mut datagram = [
+        REQUEST_MAGIC.as_slice(),
+        &nonce.to_le_bytes(),
+        PROTOCOL_HASH.as_bytes(),
+    ]
+    .concat();
+    datagram.resize(MAX_DATAGRAM, 0);
+    datagram
+}
+
+fn decode_request(datagram: &[u8]) -> Option<(u64, &str)> {
+    let rest = datagram.strip_prefix(REQUEST_MAGIC.as_slice())?;
+    let (nonce, protocol) = rest.split_at_checked(8)?;
+    let nonce = u64::from_le_bytes(nonce.try_into().ok()?);
+    let protocol = std::str::from_utf8(protocol).ok()?;
+    Some((nonce, protocol.trim_end_matches('\0')))
+}
+
+fn encode_response(nonce: u64, info: &ServerInfo) -> Option<Vec<u8>> {
+    let body = toml::to_string(info).ok()?;
+    let datagram = [
+        RESPONSE_MAGIC.as_slice(),
+        &nonce.to_le_bytes(),
+        body.as_bytes(),
+    ]
+    .concat();
+    (datagram.len() <= MAX_DATAGRAM).then_some(datagram)
+}
+
+fn decode_response(datagram: &[u8]) -> Option<(u64, ServerInfo)> {
+    let rest = datagram.strip_prefix(RESPONSE_MAGIC.as_slice())?;
+    let (nonce, body) = rest.split_at_checked(8)?;
+    let nonce = u64::from_le_bytes(nonce.try_into().ok()?);
+    let info = toml::from_str(std::str::from_utf8(body).ok()?).ok()?;
+    Some((nonce, info))
+}
+
+/// Adresse des Info-Diensts zu einer aufgelösten Spieladresse
+pub fn query_address(game: SocketAddr) -> Option<SocketAddr> {
+    Some(SocketAddr::new(
+        game.ip(),
+        game.port().checked_add(QUERY_PORT_OFFSET)?,
+    ))
+}
+
+// --- Host ---
+
+/// UDP-Sockets, auf denen der hostende Client Info-Anfragen beantwortet
+#[derive(Resource)]
+struct QueryResponder {
+    sockets: Vec<UdpSocket>,
+}
+
+/// Nicht blockierender UDP-Socket auf `ip`
+fn bind_nonblocking(ip: IpAddr, port: u16) -> std::io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((ip, port))?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+fn start_query_responder(mut commands: Commands) {
+    let port = HOST_PORT + QUERY_PORT_OFFSET;
+    // IPv6 zuerst: unter Linux nimmt dieser Socket auch IPv4 an, der zweite Bind
+    // schlägt dann erwartungsgemäß fehl. Andere Systeme brauchen beide.
+    let sockets: Vec<UdpSocket> = [Ipv6Addr::UNSPECIFIED.into(), Ipv4Addr::UNSPECIFIED.into()]
+        .into_iter()
+        .filter_map(|ip: IpAddr| {
+            bind_nonblocking(ip, port)
+                .inspect_err(|err| debug!("No server info queries on {ip}:{port}: {err}"))
+                .ok()
+        })
+        .collect();
+    if sockets.is_empty() {
+        warn!("Server info queries unavailable on port {port}");
+        return;
+    }
+    info!("Answering server info queries on port {port}");
+    commands.insert_resource(QueryResponder { sockets });
+}
+
+fn stop_query_responder(mut commands: Commands) {
+    commands.remove_resource::<QueryResponder>();
+}
+
+fn answer_queries(responder: Res<QueryResponder>, config: Option<Res<HostConfig>>) {
+    // Ohne "Configure Server" (z.B. geöffneter Singleplayer) gelten die Standardwerte
+    let config = config.map_or_else(HostConfig::default, |config| config.clone());
+    let mut buffer = [0; MAX_DATAGRAM];
+    for socket in &responder.sockets {
+        answer_socket(socket, &config, &mut buffer);
+    }
+}
+
+fn answer_socket(socket: &UdpSocket, config: &HostConfig, buffer: &mut [u8]) {
+    loop {
+        let (len, from) = match socket.recv_from(buffer) {
+            Ok(received) => received,
+            Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+            Err(err) => {
+                debug!("Server info query failed: {err}");
+                break;
+            }
+        };
+        let Some((nonce, protocol)) = decode_request(&buffer[..len]) else {
+            continue;
+        };
+        if protocol != PROTOCOL_HASH {
+            debug!("Server info query from {from} with protocol {protocol:?}");
+        }
+
+        let info = ServerInfo {
+            name: config.server_name.clone(),
+            motd: config.motd.clone(),
+            version: VERSION.to_string(),
+            protocol: PROTOCOL_HASH.to_string(),
+        };
+        let Some(response) = encode_response(nonce, &info) else {
+            continue;
+        };
+        if response.len() > len {
+            debug!("Server info query from {from} is smaller than the answer, ignored");
+        } else if let Err(err) = socket.send_to(&response, from) {
+            debug!("Could not answer server info query from {from}: {err}");
+        }
+    }
+}
+
+// --- Client ---
+
+/// Ergebnis der Abfrage eines Servers
+#[derive(Debug, Default)]
+pub struct QueryState {
+    /// `None`, solange die Adresse nicht aufgelöst ist
+    target: Option<SocketAddr>,
+    resolving: Option<Task<Result<SocketAddr, AddressError>>>,
+    pending: Option<(u64, Instant)>,
+    last_sent: Option<Instant>,
+    pub info: Option<ServerInfo>,
+    pub ping: Option<Duration>,
+    /// Adresse ungültig oder letzte Anfrage unbeantwortet
+    pub unreachable: bool,
+}
+
+impl QueryState {
+    /// Ob gerade eine Anfrage auf Antwort wartet
+    pub fn is_pending(&self) -> bool {
+        self.resolving.is_some() || self.pending.is_some()
+    }
+}
+
+/// Spalte, nach der die Server-Tabelle sortiert wird
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ServerSort {
+    Name,
+    #[default]
+    Ping,
+}
+
+/// Abfragezustand aller Server in "Join Game"
+#[derive(Resource, Default)]
+pub struct ServerQueries {
+    /// Je ein Socket für IPv4- und IPv6-Ziele, bei Bedarf geöffnet
+    sockets: [Option<UdpSocket>; 2],
+    servers: HashMap<String, QueryState>,
+    next_nonce: u64,
+    pub sort: ServerSort,
+    pub descending: bool,
+}
+
+impl ServerQueries {
+    pub fn get(&self, address: &str) -> Option<&QueryState> {
+        self.servers.get(address)
+    }
+
+    pub fn info(&self, address: &str) -> Option<&ServerInfo> {
+        self.get(address)?.info.as_ref()
+    }
+
+    /// Nimmt eine Adresse in die Abfrage auf und fragt sie beim nächsten Durchlauf
+    /// sofort ab, sofern nicht schon eine Anfrage unterwegs ist. Hostnamen werden
+    /// dafür im Hintergrund aufgelöst.
+    pub fn watch(&mut self, address: &str, default_port: u16) {
+        let state = self.servers.entry(address.to_string()).or_default();
+        if state.target.is_none() && state.resolving.is_none() {
+            if let Ok(server) = ServerAddress::parse(address, default_port) {
+                match server.literal() {
+                    Some(game) => state.target = query_address(game),
+                    None => state.resolving = Some(server.resolve_async()),
+                }
+            }
+            state.unreachable = state.target.is_none() && state.resolving.is_none();
+        }
+        if state.pending.is_none() {
+            state.last_sent = None;
+        }
+    }
+
+    /// Socket passend zur Adressfamilie des Ziels
+    fn socket(&mut self, target: SocketAddr) -> Option<&UdpSocket> {
+        let slot = &mut self.sockets[usize::from(target.is_ipv6())];
+        if slot.is_none() {
+            let any: IpAddr = if target.is_ipv6() {
+                Ipv6Addr::UNSPECIFIED.into()
+            } else {
+                Ipv4Addr::UNSPECIFIED.into()
+            };
+            match bind_nonblocking(any, 0) {
+                Ok(socket) => *slot = Some(socket),
+                Err(err) => warn!("Could not open socket for server queries to {target}: {err}"),
+            }
+        }
+        slot.as_ref()
+    }
+
+    fn sort_by(&mut self, sort: ServerSort) {
+        if self.sort == sort {
+            self.descending = !self.descending;
+        } else {
+            self.sort = sort;
+            self.descending = false;
+        }
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let (a_info, b_info) = (self.info(a), self.info(b));
+        let ordering = match self.sort {
+            ServerSort::Name => {
+                let name = |address: &str, info: Option<&ServerInfo>| {
+                    info.map_or_else(|| address.to_lowercase(), |i| i.name.to_lowercase())
+                };
+                name(a, a_info).cmp(&name(b, b_info))
+            }
+            // Unbekannter Ping sortiert immer ans Ende
+            ServerSort::Ping => {
+                let ping = |address| self.get(address).and_then(|state| state.ping);
+                match (ping(a), ping(b)) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => return Ordering::Less,
+                    (None, Some(_)) => return Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Fragt gefundene Server, Favoriten und beobachtete Adressen ab und wertet Antworten aus
+fn poll_server_queries(
+    mut queries: ResMut<ServerQueries>,
+    discovered: Option<Res<DiscoveredServers>>,
+    server_list: Res<ServerListForm>,
+    settings: Res<UserSettings>,
+) {
+    let queries = &mut *queries;
+    let default_port = settings.network.default_port;
+    for address in discovered
+        .iter()
+        .flat_map(|discovered| discovered.0.iter())
+        .chain(
+            server_list
+                .list
+                .favorites
+                .iter()
+                .chain(&server_list.list.recent)
+                .map(|entry| &entry.address),
+        )
+    {
+        if !queries.servers.contains_key(address) {
+            queries.watch(address, default_port);
+        }
+    }
+
+    let now = Instant::now();
+    let addresses: Vec<String> = queries.servers.keys().cloned().collect();
+    for address in addresses {
+        let state = queries.servers.get_mut(&address).expect("listed above");
+
+        if let Some(task) = state.resolving.as_mut() {
+            let Some(result) = check_ready(task) else {
+                continue;
+            };
+            state.resolving = None;
+            state.target = result.ok().and_then(query_address);
+            state.unreachable = state.target.is_none();
+        }
+
+        if let Some((_, sent)) = state.pending
+            && now.duration_since(sent) > QUERY_TIMEOUT
+        {
+            state.pending = None;
+            state.ping = None;
+            state.unreachable = true;
+        }
+
+        let due = state
+            .last_sent
+            .is_none_or(|last| now.duration_since(last) >= QUERY_INTERVAL);
+        let Some(target) = state.target.filter(|_| due && state.pending.is_none()) else {
+            continue;
+        };
+
+        queries.next_nonce = queries.next_nonce.wrapping_add(1);
+        let nonce = queries.next_nonce;
+        let sent = queries
+            .socket(target)
+            .is_some_and(|socket| socket.send_to(&encode_request(nonce), target).is_ok());
+        let state = queries.servers.get_mut(&address).expect("inserted above");
+        state.last_sent = Some(now);
+        if sent {
+            state.pending = Some((nonce, now));
+        } else {
+            state.unreachable = true;
+        }
+    }
+
+    let mut buffer = [0; MAX_DATAGRAM];
+    for socket in queries.sockets.iter().flatten() {
+        while let Ok((len, from)) = socket.recv_from(&mut buffer) {
+            let Some((nonce, info)) = decode_response(&buffer[..len]) else {
+                continue;
+            };
+            if let Some(state) = queries.servers.values_mut().find(|state| {
+                state.target == Some(from) && state.pending.is_some_and(|(sent, _)| sent == nonce)
+            }) {
+                let (_, sent) = state.pending.take().expect("matched above");
+                state.ping = Some(sent.elapsed());
+                state.info = Some(info);
+                state.unreachable = false;
+            }
+        }
+    }
+}
+
+// --- UI ---
+
+fn ping_text(state: Option<&QueryState>) -> String {
+    match state {
+        Some(QueryState {
+            ping: Some(ping), ..
+        }) => format!("{} ms", ping.as_millis()),
+        // Kein Info-Dienst heißt nicht offline, z.B. bei dedizierten Servern
+        Some(QueryState {
+            unreachable: true, ..
+        }) => "no answer".to_string(),
+        _ => "…".to_string(),
+    }
+}
+
+/// Kurzstatus "24 ms" für Listen außerhalb der Tabelle
+pub fn status_text(queries: &ServerQueries, address: &str) -> String {
+    let state = queries.get(address);
+    let info = state.and_then(|state| state.info.as_ref());
+    let status = ping_text(state);
+    match info.map(ServerInfo::compatibility) {
+        Some(Compatibility::Incompatible) => format!("{status} · incompatible"),
+        _ => status,
+    }
+}
+
+/// Rendert die sortierbare Tabelle der gefundenen Server
+pub fn render_server_table(
+    ui: &mut egui::Ui,
+    queries: &mut ServerQueries,
+    servers: &[String],
+) -> Option<ServerListAction> {
+    let mut servers = servers.to_vec();
+    servers.sort_by(|a, b| queries.compare(a, b));
+
+    let mut action = None;
+    let mut sort = None;
+    egui::Grid::new("server_table")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for (column, label) in [
+                (Some(ServerSort::Name), "Name"),
+                (Some(ServerSort::Ping), "Ping"),
+                (None, "Version"),
+                (None, ""),
+            ] {
+                match column {
+                    Some(column) => {
+                        let arrow = match (queries.sort == column, queries.descending) {
+                            (false, _) => "",
+                            (true, true) => " ⏷",
+                            (true, false) => " ⏶",
+                        };
+                        if ui.button(format!("{label}{arrow}")).clicked() {
+                            sort = Some(column);
+                        }
+                    }
+                    None => {
+                        ui.label(label);
+                    }
+                }
+            }
+            ui.end_row();
+
+            for address in &servers {
+                let state = queries.get(address);
+                let info = state.and_then(|state| state.info.as_ref());
+                let mismatch = info.and_then(compatibility::mismatch_text);
+                let compatible =
+                    info.is_none_or(|info| info.compatibility() != Compatibility::Incompatible);
+                // Inkompatible Server werden ausgegraut und können nicht betreten werden
+                let cell = |text: &str| {
+                    let text = egui::RichText::new(text);
+                    if compatible { text } else { text.weak() }
+                };
+
+                let name = info.map_or(address.as_str(), |info| info.name.as_str());
+                let mut label = ui.selectable_label(false, cell(name));
+                if let Some(info) = info.filter(|info| !info.motd.is_empty()) {
+                    label = label.on_hover_text(format!("{address}\n{}", info.motd));
+                } else {
+                    label = label.on_hover_text(address);
+                }
+                if label.clicked() {
+                    action = Some(ServerListAction::Select(address.clone()));
+                }
+                ui.label(cell(&ping_text(state)));
+                let version = ui.label(cell(info.map_or("?", |info| info.version.as_str())));
+                if let Some(mismatch) = &mismatch {
+                    version.on_hover_text(mismatch);
+                }
+                let join = ui
+                    .add_enabled(compatible, egui::Button::new("Join"))
+                    .on_disabled_hover_text(mismatch.unwrap_or_default());
+                if join.clicked() {
+                    action = Some(ServerListAction::Join(address.clone()));
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(column) = sort {
+        queries.sort_by(column);
+    }
+    action
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_datagrams_round_trip() {
+        let info = ServerInfo {
+            name: "Home".to_string(),
+            motd: "Welcome!".to_string(),
+            version: VERSION.to_string(),
+            protocol: PROTOCOL_HASH.to_string(),
+        };
+
+        assert_eq!(
+            decode_request(&encode_request(42)),
+            Some((42, PROTOCOL_HASH))
+        );
+        assert_eq!(encode_request(42).len(), MAX_DATAGRAM);
+        assert_eq!(decode_request(b"FOSQ?short"), None);
+        let response = encode_response(42, &info).unwrap();
+        assert!(response.len() <= encode_request(42).len());
+        assert_eq!(decode_response(&response), Some((42, info)));
+        assert_eq!(decode_response(&encode_request(42)), None);
+    }
+
+    #[test]
+    fn query_port_sits_next_to_the_game_port() {
+        assert_eq!(
+            query_address("127.0.0.1:9000".parse().unwrap()),
+            Some("127.0.0.1:9001".parse().unwrap())
+        );
+        assert_eq!(
+            query_address("[::1]:8080".parse().unwrap()),
+            Some("[::1]:8081".parse().unwrap())
+        );
+        assert_eq!(query_address("127.0.0.1:65535".parse().unwrap()), None);
+    }
+}
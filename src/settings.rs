@@ -0,0 +1,762 @@
+use {
+    crate::{input::InputBindings, paths},
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    chicken::states::{
+        events::menu::settings::SetSettingsMenu, states::menu::main::MainMenuScreen,
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Wartezeit nach der letzten Änderung, bevor die Einstellungen geschrieben werden
+const SAVE_DEBOUNCE_SECS: f32 = 0.5;
+
+/// Plugin für die persistenten Benutzereinstellungen.
+///
+/// Lädt `UserSettings` beim Aufbau der App aus der TOML-Datei in [`SettingsPath`]
+/// und schreibt sie nach Änderungen (entprellt) wieder zurück.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // Ein vorher eingefügter SettingsPath (z.B. per CLI) hat Vorrang
+        let path = app
+            .world()
+            .get_resource::<SettingsPath>()
+            .cloned()
+            .unwrap_or_default();
+        let settings = UserSettings::load_or_default(&path.0);
+
+        app.insert_resource(path)
+            .insert_resource(settings)
+            .init_resource::<SettingsSaveTimer>()
+            .add_sub_state::<SettingsMenuScreen>()
+            .add_observer(on_set_settings_screen)
+            .add_systems(
+                Update,
+                (
+                    mark_settings_dirty.run_if(
+                        resource_changed::<UserSettings>.and(not(resource_added::<UserSettings>)),
+                    ),
+                    save_settings_debounced,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Pfad der Einstellungsdatei
+#[derive(Resource, Debug, Clone)]
+pub struct SettingsPath(pub PathBuf);
+
+impl Default for SettingsPath {
+    fn default() -> Self {
+        Self(paths::config_dir().join("settings.toml"))
+    }
+}
+
+/// Alle Benutzereinstellungen, gruppiert nach Kategorie
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub controls: ControlsSettings,
+    pub chat: ChatSettings,
+    pub network: NetworkSettings,
+    pub interface: InterfaceSettings,
+}
+
+/// Anzeige-Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub window_mode: WindowModeSetting,
+    /// Fenstergröße bzw. Vollbild-Auflösung in physischen Pixeln
+    pub resolution: [u32; 2],
+    /// Name des Monitors; `None` = primärer Monitor
+    pub monitor: Option<String>,
+    pub vsync: bool,
+    /// Maximale Bildrate, 0 = unbegrenzt
+    pub frame_rate_cap: u32,
+    /// Skalierung der egui-Oberfläche
+    pub ui_scale: f32,
+}
+
+impl VideoSettings {
+    pub const MIN_RESOLUTION: [u32; 2] = [640, 360];
+    pub const FRAME_RATE_CAP: RangeInclusive<u32> = 30..=360;
+    pub const UI_SCALE: RangeInclusive<f32> = 0.5..=3.0;
+
+    /// Änderungen, nach denen das Bild unbrauchbar sein kann und die deshalb
+    /// bestätigt werden müssen. Randlos ignoriert die Auflösung; ein Fenster wechselt
+    /// nur den Monitor, ohne dass sich am Bild etwas ändert.
+    pub fn is_risky_change_from(&self, previous: &VideoSettings) -> bool {
+        self.window_mode != previous.window_mode
+            || (self.resolution != previous.resolution
+                && self.window_mode != WindowModeSetting::Borderless)
+            || (self.monitor != previous.monitor && self.window_mode != WindowModeSetting::Windowed)
+    }
+
+    /// Übernimmt die riskanten Felder aus `previous`
+    pub fn restore_display_from(&mut self, previous: &VideoSettings) {
+        self.window_mode = previous.window_mode;
+        self.resolution = previous.resolution;
+        self.monitor = previous.monitor.clone();
+    }
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowModeSetting::Windowed,
+            resolution: [1280, 720],
+            monitor: None,
+            vsync: true,
+            frame_rate_cap: 0,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowModeSetting {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub const ALL: [WindowModeSetting; 3] = [
+        WindowModeSetting::Windowed,
+        WindowModeSetting::Borderless,
+        WindowModeSetting::Fullscreen,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::Borderless => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+}
+
+/// Audio-Einstellungen. Alle Lautstärken linear von 0.0 bis 1.0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    pub ui_volume: f32,
+    pub chat_volume: f32,
+    /// Stummschalten, solange das Fenster keinen Fokus hat
+    pub mute_when_unfocused: bool,
+}
+
+impl AudioSettings {
+    pub const VOLUME: RangeInclusive<f32> = 0.0..=1.0;
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.8,
+            music_volume: 0.6,
+            effects_volume: 1.0,
+            ui_volume: 0.5,
+            chat_volume: 0.7,
+            mute_when_unfocused: true,
+        }
+    }
+}
+
+/// Steuerungs-Einstellungen
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlsSettings {
+    pub bindings: InputBindings,
+}
+
+/// Chat-Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatSettings {
+    /// Anzeigedauer von Server-Fehlermeldungen in Sekunden
+    pub error_display_secs: f32,
+    /// Eigene @mentions hervorheben
+    pub highlight_mentions: bool,
+    /// Hinweis "ENTER oder T" anzeigen, solange der Chat geschlossen ist
+    pub show_hint: bool,
+    /// Deckkraft des Chat-Hintergrunds (0-255)
+    pub background_opacity: u8,
+}
+
+impl ChatSettings {
+    pub const ERROR_DISPLAY_SECS: RangeInclusive<f32> = 1.0..=30.0;
+}
+
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            error_display_secs: 5.0,
+            highlight_mentions: true,
+            show_hint: true,
+            background_opacity: 200,
+        }
+    }
+}
+
+/// Netzwerk-Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// Port, der bei manuellen Adressen ohne Port angenommen wird
+    pub default_port: u16,
+    /// Anzahl der Suchzyklen bei der LAN-Suche
+    pub discovery_cycles: u32,
+    /// Versuche nach einem Verbindungsabbruch; 0 = nicht wiederverbinden
+    pub reconnect_attempts: u32,
+    /// Wartezeit vor dem ersten Versuch, verdoppelt sich mit jedem weiteren
+    pub reconnect_delay_secs: f32,
+}
+
+impl NetworkSettings {
+    pub const DISCOVERY_CYCLES: RangeInclusive<u32> = 1..=20;
+    pub const RECONNECT_ATTEMPTS: RangeInclusive<u32> = 0..=10;
+    pub const RECONNECT_DELAY_SECS: RangeInclusive<f32> = 0.5..=10.0;
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            default_port: 8080,
+            discovery_cycles: 5,
+            reconnect_attempts: 5,
+            reconnect_delay_secs: 1.0,
+        }
+    }
+}
+
+/// Oberflächen-Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InterfaceSettings {
+    /// Debug-Fenster mit den aktuellen States anzeigen
+    pub show_state_overlay: bool,
+    /// bevy-inspector-egui World Inspector anzeigen
+    pub show_world_inspector: bool,
+    pub language: Language,
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            show_state_overlay: true,
+            show_world_inspector: true,
+            language: Language::default(),
+        }
+    }
+}
+
+/// Sprache der übersetzten Texte. Bisher sind das nur die Chat-Fehlermeldungen;
+/// die Menüs gibt es nur auf Englisch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[default]
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// Sprachcode wie `de` oder `de-DE`
+    pub fn from_code(code: &str) -> Result<Self, String> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(primary))
+            .ok_or_else(|| format!("unsupported language {code:?}, expected en or de"))
+    }
+}
+
+/// Sprache aus `--lang`; gilt nur für diesen Lauf und wird nicht gespeichert
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageOverride(pub Language);
+
+impl UserSettings {
+    /// Lädt die Einstellungen; fehlende oder kaputte Dateien ergeben die Standardwerte.
+    /// Eine nicht lesbare Datei wird als `.bak` zur Seite gelegt statt überschrieben.
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            info!("No settings file at {}, using defaults", path.display());
+            return Self::default();
+        }
+
+        match Self::load(path) {
+            Ok(mut settings) => {
+                settings.validate();
+                settings
+            }
+            Err(err) => {
+                warn!("Failed to load settings: {err:#}");
+                let backup = path.with_extension("toml.bak");
+                if let Err(err) = fs::rename(path, &backup) {
+                    warn!("Failed to back up broken settings file: {err}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// Bringt alle Werte in ihre gültigen Bereiche
+    pub fn validate(&mut self) {
+        for (axis, min) in self
+            .video
+            .resolution
+            .iter_mut()
+            .zip(VideoSettings::MIN_RESOLUTION)
+        {
+            if *axis < min {
+                warn!("Setting video.resolution below {min}, clamping");
+                *axis = min;
+            }
+        }
+        if self.video.frame_rate_cap != 0 {
+            clamp_setting(
+                "video.frame_rate_cap",
+                &mut self.video.frame_rate_cap,
+                VideoSettings::FRAME_RATE_CAP,
+            );
+        }
+        let defaults = Self::default();
+        clamp_finite_setting(
+            "video.ui_scale",
+            &mut self.video.ui_scale,
+            VideoSettings::UI_SCALE,
+            defaults.video.ui_scale,
+        );
+        for (name, volume, default) in [
+            (
+                "audio.master_volume",
+                &mut self.audio.master_volume,
+                defaults.audio.master_volume,
+            ),
+            (
+                "audio.music_volume",
+                &mut self.audio.music_volume,
+                defaults.audio.music_volume,
+            ),
+            (
+                "audio.effects_volume",
+                &mut self.audio.effects_volume,
+                defaults.audio.effects_volume,
+            ),
+            (
+                "audio.ui_volume",
+                &mut self.audio.ui_volume,
+                defaults.audio.ui_volume,
+            ),
+            (
+                "audio.chat_volume",
+                &mut self.audio.chat_volume,
+                defaults.audio.chat_volume,
+            ),
+        ] {
+            clamp_finite_setting(name, volume, AudioSettings::VOLUME, default);
+        }
+        clamp_finite_setting(
+            "chat.error_display_secs",
+            &mut self.chat.error_display_secs,
+            ChatSettings::ERROR_DISPLAY_SECS,
+            defaults.chat.error_display_secs,
+        );
+        clamp_setting(
+            "network.discovery_cycles",
+            &mut self.network.discovery_cycles,
+            NetworkSettings::DISCOVERY_CYCLES,
+        );
+        clamp_setting(
+            "network.reconnect_attempts",
+            &mut self.network.reconnect_attempts,
+            NetworkSettings::RECONNECT_ATTEMPTS,
+        );
+        clamp_finite_setting(
+            "network.reconnect_delay_secs",
+            &mut self.network.reconnect_delay_secs,
+            NetworkSettings::RECONNECT_DELAY_SECS,
+            defaults.network.reconnect_delay_secs,
+        );
+        if self.network.default_port == 0 {
+            warn!("Setting network.default_port must not be 0, using default");
+            self.network.default_port = defaults.network.default_port;
+        }
+        self.controls.bindings.fill_missing();
+    }
+
+    /// Wirksame Sprache: `--lang` vor der gespeicherten Einstellung
+    pub fn language(&self, language_override: Option<&LanguageOverride>) -> Language {
+        language_override.map_or(self.interface.language, |language| language.0)
+    }
+
+    /// Setzt eine einzelne Kategorie auf ihre Standardwerte zurück
+    pub fn restore_defaults(&mut self, category: SettingsMenuScreen) {
+        let defaults = Self::default();
+        match category {
+            SettingsMenuScreen::Overview => *self = defaults,
+            SettingsMenuScreen::Video => self.video = defaults.video,
+            SettingsMenuScreen::Audio => self.audio = defaults.audio,
+            SettingsMenuScreen::Controls => self.controls = defaults.controls,
+            SettingsMenuScreen::Chat => self.chat = defaults.chat,
+            SettingsMenuScreen::Network => self.network = defaults.network,
+            SettingsMenuScreen::Interface => self.interface = defaults.interface,
+        }
+    }
+}
+
+fn clamp_setting<T: PartialOrd + Copy + std::fmt::Debug>(
+    name: &str,
+    value: &mut T,
+    range: RangeInclusive<T>,
+) {
+    let clamped = if *value < *range.start() {
+        *range.start()
+    } else if *value > *range.end() {
+        *range.end()
+    } else {
+        return;
+    };
+    warn!("Setting {name} = {value:?} out of range, using {clamped:?}");
+    *value = clamped;
+}
+
+/// Wie [`clamp_setting`], ersetzt aber NaN und ±∞ durch `default`. NaN fiele sonst
+/// durch jeden Vergleich und bliebe stehen, ∞ landete auf der Bereichsgrenze.
+fn clamp_finite_setting(name: &str, value: &mut f32, range: RangeInclusive<f32>, default: f32) {
+    if value.is_finite() {
+        clamp_setting(name, value, range);
+    } else {
+        warn!("Setting {name} = {value} is not a number, using default {default}");
+        *value = default;
+    }
+}
+
+/// Unterseiten des Settings-Menüs
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(MainMenuScreen = MainMenuScreen::Settings)]
+pub enum SettingsMenuScreen {
+    #[default]
+    Overview,
+    Video,
+    Audio,
+    Controls,
+    Chat,
+    Network,
+    Interface,
+}
+
+impl SettingsMenuScreen {
+    pub const CATEGORIES: [SettingsMenuScreen; 6] = [
+        SettingsMenuScreen::Video,
+        SettingsMenuScreen::Audio,
+        SettingsMenuScreen::Controls,
+        SettingsMenuScreen::Chat,
+        SettingsMenuScreen::Network,
+        SettingsMenuScreen::Interface,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsMenuScreen::Overview => "Settings",
+            SettingsMenuScreen::Video => "Video",
+            SettingsMenuScreen::Audio => "Audio",
+            SettingsMenuScreen::Controls => "Controls",
+            SettingsMenuScreen::Chat => "Chat",
+            SettingsMenuScreen::Network => "Network",
+            SettingsMenuScreen::Interface => "Interface",
+        }
+    }
+}
+
+/// Navigation innerhalb des Settings-Menüs
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetSettingsScreen {
+    Open(SettingsMenuScreen),
+    /// Zurück zur Übersicht bzw. aus der Übersicht zurück ins Hauptmenü
+    Back,
+}
+
+fn on_set_settings_screen(
+    event: On<SetSettingsScreen>,
+    mut commands: Commands,
+    current: Option<Res<State<SettingsMenuScreen>>>,
+    mut next: ResMut<NextState<SettingsMenuScreen>>,
+) {
+    let Some(current) = current else {
+        return;
+    };
+
+    match *event {
+        SetSettingsScreen::Open(screen) => next.set(screen),
+        SetSettingsScreen::Back => match current.get() {
+            SettingsMenuScreen::Overview => commands.trigger(SetSettingsMenu::Back),
+            _ => next.set(SettingsMenuScreen::Overview),
+        },
+    }
+}
+
+/// Entprell-Timer fürs Speichern; `None` solange nichts zu speichern ist
+#[derive(Resource, Default)]
+struct SettingsSaveTimer(Option<Timer>);
+
+fn mark_settings_dirty(mut timer: ResMut<SettingsSaveTimer>) {
+    timer.0 = Some(Timer::from_seconds(SAVE_DEBOUNCE_SECS, TimerMode::Once));
+}
+
+fn save_settings_debounced(
+    mut timer: ResMut<SettingsSaveTimer>,
+    time: Res<Time>,
+    settings: Res<UserSettings>,
+    path: Res<SettingsPath>,
+) {
+    let Some(ref mut pending) = timer.0 else {
+        return;
+    };
+    if !pending.tick(time.delta()).just_finished() {
+        return;
+    }
+    timer.0 = None;
+
+    match settings.save(&path.0) {
+        Ok(()) => debug!("Saved settings to {}", path.0.display()),
+        Err(err) => error!("Failed to save settings: {err:#}"),
+    }
+}
+
+// --- UI ---
+
+/// Rendert die Chat-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_chat_settings(ui: &mut egui::Ui, chat: &mut ChatSettings) -> bool {
+    let mut changed = false;
+    changed |= ui
+        .add(
+            egui::Slider::new(
+                &mut chat.error_display_secs,
+                ChatSettings::ERROR_DISPLAY_SECS,
+            )
+            .text("Error display (s)"),
+        )
+        .changed();
+    changed |= ui
+        .checkbox(&mut chat.highlight_mentions, "Highlight own mentions")
+        .changed();
+    changed |= ui
+        .checkbox(&mut chat.show_hint, "Show \"press ENTER to chat\" hint")
+        .changed();
+    changed |= ui
+        .add(egui::Slider::new(&mut chat.background_opacity, 0..=255).text("Background opacity"))
+        .changed();
+    changed
+}
+
+/// Rendert die Netzwerk-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_network_settings(ui: &mut egui::Ui, network: &mut NetworkSettings) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label("Default port");
+        changed |= ui
+            .add(egui::DragValue::new(&mut network.default_port).range(1..=u16::MAX))
+            .changed();
+    });
+    changed |= ui
+        .add(
+            egui::Slider::new(
+                &mut network.discovery_cycles,
+                NetworkSettings::DISCOVERY_CYCLES,
+            )
+            .text("LAN discovery cycles"),
+        )
+        .changed();
+    changed |= ui
+        .add(
+            egui::Slider::new(
+                &mut network.reconnect_attempts,
+                NetworkSettings::RECONNECT_ATTEMPTS,
+            )
+            .text("Reconnect attempts"),
+        )
+        .on_hover_text("0 returns to the menu right away when the connection drops")
+        .changed();
+    changed |= ui
+        .add_enabled(
+            network.reconnect_attempts > 0,
+            egui::Slider::new(
+                &mut network.reconnect_delay_secs,
+                NetworkSettings::RECONNECT_DELAY_SECS,
+            )
+            .suffix(" s")
+            .text("First reconnect delay"),
+        )
+        .changed();
+    changed
+}
+
+/// Rendert die Oberflächen-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_interface_settings(ui: &mut egui::Ui, interface: &mut InterfaceSettings) -> bool {
+    let mut changed = false;
+    changed |= ui
+        .checkbox(
+            &mut interface.show_state_overlay,
+            "Show state debug overlay",
+        )
+        .changed();
+    changed |= ui
+        .checkbox(&mut interface.show_world_inspector, "Show world inspector")
+        .changed();
+    ui.horizontal(|ui| {
+        ui.label("Chat message language");
+        egui::ComboBox::from_id_salt("interface_language")
+            .selected_text(interface.language.label())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    changed |= ui
+                        .selectable_value(&mut interface.language, language, language.label())
+                        .changed();
+                }
+            });
+    });
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_roundtrip_through_toml() {
+        let settings = UserSettings::default();
+        let content = toml::to_string_pretty(&settings).unwrap();
+        assert_eq!(toml::from_str::<UserSettings>(&content).unwrap(), settings);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let settings: UserSettings = toml::from_str("[chat]\nshow_hint = false\n").unwrap();
+        assert!(!settings.chat.show_hint);
+        assert_eq!(settings.chat.error_display_secs, 5.0);
+        assert_eq!(settings.network, NetworkSettings::default());
+    }
+
+    #[test]
+    fn validate_clamps_out_of_range_values() {
+        let mut settings = UserSettings::default();
+        settings.chat.error_display_secs = 500.0;
+        settings.network.discovery_cycles = 0;
+        settings.network.default_port = 0;
+        settings.validate();
+
+        assert_eq!(settings.chat.error_display_secs, 30.0);
+        assert_eq!(settings.network.discovery_cycles, 1);
+        assert_eq!(settings.network.default_port, 8080);
+    }
+
+    #[test]
+    fn validate_replaces_non_finite_values_with_defaults() {
+        let mut settings = UserSettings::default();
+        settings.video.ui_scale = f32::NAN;
+        settings.audio.music_volume = f32::INFINITY;
+        settings.network.reconnect_delay_secs = f32::NEG_INFINITY;
+        settings.validate();
+
+        assert_eq!(settings, UserSettings::default());
+    }
+
+    #[test]
+    fn only_effective_display_changes_need_confirmation() {
+        let windowed = VideoSettings::default();
+        let borderless = VideoSettings {
+            window_mode: WindowModeSetting::Borderless,
+            ..default()
+        };
+        let other_monitor = |video: &VideoSettings| VideoSettings {
+            monitor: Some("DP-2".to_string()),
+            ..video.clone()
+        };
+        let other_resolution = |video: &VideoSettings| VideoSettings {
+            resolution: [1920, 1080],
+            ..video.clone()
+        };
+
+        assert!(borderless.is_risky_change_from(&windowed));
+        assert!(!other_monitor(&windowed).is_risky_change_from(&windowed));
+        assert!(other_resolution(&windowed).is_risky_change_from(&windowed));
+        assert!(other_monitor(&borderless).is_risky_change_from(&borderless));
+        assert!(!other_resolution(&borderless).is_risky_change_from(&borderless));
+    }
+
+    #[test]
+    fn language_codes_are_parsed() {
+        assert_eq!(Language::from_code("en"), Ok(Language::English));
+        assert_eq!(Language::from_code("de-AT"), Ok(Language::German));
+        assert_eq!(Language::from_code("DE"), Ok(Language::German));
+        assert!(Language::from_code("fr").is_err());
+
+        let settings = UserSettings::default();
+        assert_eq!(settings.language(None), Language::German);
+        assert_eq!(
+            settings.language(Some(&LanguageOverride(Language::English))),
+            Language::English
+        );
+    }
+
+    #[test]
+    fn restore_defaults_only_touches_one_category() {
+        let mut settings = UserSettings::default();
+        settings.chat.show_hint = false;
+        settings.interface.show_world_inspector = false;
+        settings.restore_defaults(SettingsMenuScreen::Chat);
+
+        assert!(settings.chat.show_hint);
+        assert!(!settings.interface.show_world_inspector);
+    }
+}
@@ -0,0 +1,377 @@
+use {
+    crate::settings::{UserSettings, VideoSettings, WindowModeSetting},
+    bevy::{
+        prelude::*,
+        window::{
+            Monitor, MonitorSelection, PresentMode, PrimaryMonitor, PrimaryWindow,
+            VideoModeSelection, WindowMode, WindowPosition,
+        },
+        winit::{UpdateMode, WinitSettings},
+    },
+    bevy_egui::{EguiContextSettings, EguiContexts, egui},
+    std::time::Duration,
+};
+
+/// Sekunden, bis riskante Anzeigeänderungen ohne Bestätigung zurückgenommen werden
+const REVERT_COUNTDOWN_SECS: f32 = 15.0;
+
+/// Auflösungen, falls der Monitor keine Video-Modi meldet
+const FALLBACK_RESOLUTIONS: [[u32; 2]; 6] = [
+    [3840, 2160],
+    [2560, 1440],
+    [1920, 1080],
+    [1600, 900],
+    [1280, 720],
+    [1024, 576],
+];
+
+/// Plugin, das die Video-Einstellungen live auf das primäre Fenster anwendet
+pub struct VideoPlugin;
+
+impl Plugin for VideoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppliedVideoSettings>()
+            .init_resource::<VideoRevert>()
+            .add_systems(
+                Update,
+                (
+                    apply_video_settings.run_if(resource_changed::<UserSettings>),
+                    tick_video_revert,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Zuletzt auf das Fenster angewendete Einstellungen
+#[derive(Resource, Default)]
+struct AppliedVideoSettings(Option<VideoSettings>);
+
+/// Offene "Einstellungen behalten?"-Abfrage nach einer riskanten Änderung
+#[derive(Resource, Default)]
+pub struct VideoRevert {
+    pending: Option<PendingRevert>,
+    /// Die nächste Änderung ist selbst ein Zurücksetzen und braucht keine Abfrage
+    reverting: bool,
+}
+
+struct PendingRevert {
+    previous: VideoSettings,
+    timer: Timer,
+}
+
+impl VideoRevert {
+    pub fn keep(&mut self) {
+        self.pending = None;
+    }
+
+    pub fn revert(&mut self, settings: &mut UserSettings) {
+        if let Some(pending) = self.pending.take() {
+            settings.video.restore_display_from(&pending.previous);
+            self.reverting = true;
+        }
+    }
+}
+
+fn apply_video_settings(
+    settings: Res<UserSettings>,
+    mut applied: ResMut<AppliedVideoSettings>,
+    mut revert: ResMut<VideoRevert>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    monitors: Query<(Entity, &Monitor, Has<PrimaryMonitor>)>,
+    mut egui_settings: Query<&mut EguiContextSettings>,
+    winit: Option<ResMut<WinitSettings>>,
+) {
+    let video = &settings.video;
+    if applied.0.as_ref() == Some(video) {
+        return;
+    }
+
+    // Beim Start wird ohne Abfrage angewendet
+    if let Some(previous) = &applied.0
+        && video.is_risky_change_from(previous)
+        && !revert.reverting
+    {
+        // Bei mehreren Änderungen hintereinander gilt der Zustand vor der ersten
+        let previous = match revert.pending.take() {
+            Some(pending) => pending.previous,
+            None => previous.clone(),
+        };
+        revert.pending = Some(PendingRevert {
+            previous,
+            timer: Timer::from_seconds(REVERT_COUNTDOWN_SECS, TimerMode::Once),
+        });
+    }
+    revert.reverting = false;
+
+    let monitor = select_monitor(video.monitor.as_deref(), &monitors);
+    let monitor_changed = applied
+        .0
+        .as_ref()
+        .is_some_and(|previous| previous.monitor != video.monitor);
+    apply_to_window(video, &mut window, monitor, monitor_changed);
+
+    for mut context in &mut egui_settings {
+        context.scale_factor = video.ui_scale;
+    }
+    if let Some(mut winit) = winit {
+        winit.focused_mode = focused_update_mode(video.frame_rate_cap);
+    }
+
+    applied.0 = Some(video.clone());
+}
+
+/// Sucht den Monitor anhand seines Namens, sonst den primären
+fn select_monitor<'a>(
+    name: Option<&str>,
+    monitors: &'a Query<(Entity, &Monitor, Has<PrimaryMonitor>)>,
+) -> Option<(Entity, &'a Monitor)> {
+    let by_name = name.and_then(|name| {
+        monitors
+            .iter()
+            .find(|(_, monitor, _)| monitor.name.as_deref() == Some(name))
+    });
+    by_name
+        .or_else(|| monitors.iter().find(|(_, _, primary)| *primary))
+        .map(|(entity, monitor, _)| (entity, monitor))
+}
+
+/// `monitor_changed`: Ein Fenster zieht auch ohne Moduswechsel auf den neuen Monitor um
+fn apply_to_window(
+    video: &VideoSettings,
+    window: &mut Window,
+    monitor: Option<(Entity, &Monitor)>,
+    monitor_changed: bool,
+) {
+    let selection = monitor.map_or(MonitorSelection::Primary, |(entity, _)| {
+        MonitorSelection::Entity(entity)
+    });
+    let [width, height] = video.resolution;
+
+    let mode = match video.window_mode {
+        WindowModeSetting::Windowed => WindowMode::Windowed,
+        WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen(selection),
+        WindowModeSetting::Fullscreen => {
+            // Passenden Video-Modus mit der höchsten Bildwiederholrate wählen
+            let video_mode = monitor
+                .and_then(|(_, monitor)| {
+                    monitor
+                        .video_modes
+                        .iter()
+                        .filter(|mode| mode.physical_size == UVec2::new(width, height))
+                        .max_by_key(|mode| mode.refresh_rate_millihertz)
+                })
+                .map_or(VideoModeSelection::Current, |mode| {
+                    VideoModeSelection::Specific(*mode)
+                });
+            WindowMode::Fullscreen(selection, video_mode)
+        }
+    };
+
+    let mode_changed = window.mode != mode;
+    if mode_changed {
+        window.mode = mode;
+    }
+    if mode == WindowMode::Windowed && (mode_changed || monitor_changed) {
+        window.position = WindowPosition::Centered(selection);
+    }
+    if mode == WindowMode::Windowed
+        && (window.resolution.physical_width() != width
+            || window.resolution.physical_height() != height)
+    {
+        window.resolution.set_physical_resolution(width, height);
+    }
+
+    let present_mode = if video.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+fn tick_video_revert(
+    time: Res<Time<Real>>,
+    mut revert: ResMut<VideoRevert>,
+    mut settings: ResMut<UserSettings>,
+) {
+    let Some(pending) = revert.pending.as_mut() else {
+        return;
+    };
+    if pending.timer.tick(time.delta()).just_finished() {
+        info!("Display settings not confirmed, reverting");
+        revert.revert(&mut settings);
+    }
+}
+
+/// Bildratenbegrenzung über winit: Die Event-Schleife wartet bis zum nächsten Frame,
+/// statt den Hauptthread schlafen zu legen. Eingaben wecken sie früher.
+fn focused_update_mode(frame_rate_cap: u32) -> UpdateMode {
+    match frame_rate_cap {
+        0 => UpdateMode::Continuous,
+        cap => UpdateMode::reactive_low_power(Duration::from_secs_f64(1.0 / f64::from(cap))),
+    }
+}
+
+// --- UI ---
+
+/// Monitor-Informationen für die Einstellungsseite
+pub struct MonitorInfo {
+    pub name: String,
+    pub primary: bool,
+    pub resolutions: Vec<[u32; 2]>,
+}
+
+impl MonitorInfo {
+    pub fn collect<'a>(monitors: impl Iterator<Item = (&'a Monitor, bool)>) -> Vec<Self> {
+        monitors
+            .enumerate()
+            .map(|(index, (monitor, primary))| {
+                let mut resolutions: Vec<[u32; 2]> = monitor
+                    .video_modes
+                    .iter()
+                    .map(|mode| mode.physical_size.to_array())
+                    .collect();
+                resolutions.sort_unstable_by(|a, b| b.cmp(a));
+                resolutions.dedup();
+                Self {
+                    name: monitor
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("Monitor {}", index + 1)),
+                    primary,
+                    resolutions,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rendert die Video-Seite. Gibt `true` zurück, wenn sich etwas geändert hat.
+pub fn render_video_settings(
+    ui: &mut egui::Ui,
+    video: &mut VideoSettings,
+    monitors: &[MonitorInfo],
+) -> bool {
+    let mut changed = false;
+
+    egui::ComboBox::from_label("Window mode")
+        .selected_text(video.window_mode.label())
+        .show_ui(ui, |ui| {
+            for mode in WindowModeSetting::ALL {
+                changed |= ui
+                    .selectable_value(&mut video.window_mode, mode, mode.label())
+                    .changed();
+            }
+        });
+
+    let selected_monitor = monitors
+        .iter()
+        .find(|monitor| Some(&monitor.name) == video.monitor.as_ref())
+        .or_else(|| monitors.iter().find(|monitor| monitor.primary));
+
+    egui::ComboBox::from_label("Monitor")
+        .selected_text(video.monitor.as_deref().unwrap_or("Primary"))
+        .show_ui(ui, |ui| {
+            changed |= ui
+                .selectable_value(&mut video.monitor, None, "Primary")
+                .changed();
+            for monitor in monitors {
+                changed |= ui
+                    .selectable_value(
+                        &mut video.monitor,
+                        Some(monitor.name.clone()),
+                        &monitor.name,
+                    )
+                    .changed();
+            }
+        });
+
+    let resolutions = match selected_monitor {
+        Some(monitor) if !monitor.resolutions.is_empty() => monitor.resolutions.as_slice(),
+        _ => FALLBACK_RESOLUTIONS.as_slice(),
+    };
+    let [width, height] = video.resolution;
+    egui::ComboBox::from_label("Resolution")
+        .selected_text(format!("{width} × {height}"))
+        .show_ui(ui, |ui| {
+            for &[w, h] in resolutions {
+                changed |= ui
+                    .selectable_value(&mut video.resolution, [w, h], format!("{w} × {h}"))
+                    .changed();
+            }
+        });
+
+    changed |= ui.checkbox(&mut video.vsync, "VSync").changed();
+
+    ui.horizontal(|ui| {
+        let mut limited = video.frame_rate_cap > 0;
+        if ui.checkbox(&mut limited, "Limit frame rate").changed() {
+            video.frame_rate_cap = if limited { 60 } else { 0 };
+            changed = true;
+        }
+        if limited {
+            changed |= ui
+                .add(
+                    egui::Slider::new(&mut video.frame_rate_cap, VideoSettings::FRAME_RATE_CAP)
+                        .suffix(" fps"),
+                )
+                .changed();
+        }
+    });
+
+    // Skalierung erst beim Loslassen übernehmen, sonst springt der Slider unter der Maus
+    let drag_id = ui.id().with("ui_scale_drag");
+    let mut ui_scale = ui
+        .data(|data| data.get_temp::<f32>(drag_id))
+        .unwrap_or(video.ui_scale);
+    let response = ui.add(
+        egui::Slider::new(&mut ui_scale, VideoSettings::UI_SCALE)
+            .step_by(0.05)
+            .text("UI scale"),
+    );
+    if response.dragged() {
+        ui.data_mut(|data| data.insert_temp(drag_id, ui_scale));
+    } else {
+        ui.data_mut(|data| data.remove::<f32>(drag_id));
+        if ui_scale != video.ui_scale {
+            video.ui_scale = ui_scale;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Zeigt nach riskanten Änderungen den Countdown-Dialog
+pub fn render_video_revert_dialog(
+    mut contexts: EguiContexts,
+    mut revert: ResMut<VideoRevert>,
+    mut settings: ResMut<UserSettings>,
+) {
+    let Some(remaining) = revert
+        .pending
+        .as_ref()
+        .map(|pending| pending.timer.remaining_secs().ceil())
+    else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Modal::new(egui::Id::new("video_revert")).show(ctx, |ui| {
+        ui.heading("Keep these display settings?");
+        ui.label(format!("Reverting in {remaining} seconds."));
+        ui.horizontal(|ui| {
+            if ui.button("Keep").clicked() {
+                revert.keep();
+            }
+            if ui.button("Revert").clicked() {
+                revert.revert(&mut settings);
+            }
+        });
+    });
+}
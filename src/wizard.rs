@@ -0,0 +1,108 @@
+use bevy_egui::egui;
+
+/// Vom Spieler im Assistenten ausgelöste Aktion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardAction {
+    Previous,
+    Next,
+    /// "Start" im letzten Schritt
+    Finish,
+    Cancel,
+}
+
+/// Mehrstufiger Assistent für die Menü-Abläufe (Neues Spiel, Server hosten).
+///
+/// Zeichnet die Schrittanzeige, den Inhalt des aktuellen Schritts und die
+/// Navigation. Der Schrittinhalt liefert, ob er gültig ist; solange nicht, bleibt
+/// "Next" bzw. der Start-Button deaktiviert. Die Formulardaten liegen in Resources
+/// und bleiben daher beim Zurückblättern erhalten.
+pub struct Wizard<'a> {
+    steps: &'a [&'a str],
+    current: usize,
+    finish_label: &'a str,
+}
+
+impl<'a> Wizard<'a> {
+    pub fn new(steps: &'a [&'a str], current: usize) -> Self {
+        Self {
+            steps,
+            current: current.min(steps.len().saturating_sub(1)),
+            finish_label: "Start",
+        }
+    }
+
+    pub fn finish_label(mut self, label: &'a str) -> Self {
+        self.finish_label = label;
+        self
+    }
+
+    fn is_last(&self) -> bool {
+        self.current + 1 == self.steps.len()
+    }
+
+    /// Rendert den Assistenten. `step` zeichnet den aktuellen Schritt und gibt
+    /// zurück, ob seine Eingaben gültig sind.
+    pub fn show(
+        self,
+        ui: &mut egui::Ui,
+        step: impl FnOnce(&mut egui::Ui) -> bool,
+    ) -> Option<WizardAction> {
+        self.render_progress(ui);
+        ui.separator();
+
+        let valid = step(ui);
+
+        ui.separator();
+        let mut action = None;
+        ui.horizontal(|ui| {
+            if self.current > 0 && ui.button("← Previous").clicked() {
+                action = Some(WizardAction::Previous);
+            }
+
+            let (label, forward) = if self.is_last() {
+                (self.finish_label, WizardAction::Finish)
+            } else {
+                ("Next →", WizardAction::Next)
+            };
+            if ui
+                .add_enabled(valid, egui::Button::new(label))
+                .on_disabled_hover_text("Fix the highlighted fields first")
+                .clicked()
+            {
+                action = Some(forward);
+            }
+
+            if ui.button("Cancel").clicked() {
+                action = Some(WizardAction::Cancel);
+            }
+        });
+        action
+    }
+
+    fn render_progress(&self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for (index, title) in self.steps.iter().enumerate() {
+                if index > 0 {
+                    ui.weak("›");
+                }
+                let text = format!("{}. {title}", index + 1);
+                if index == self.current {
+                    ui.strong(text);
+                } else if index < self.current {
+                    ui.label(format!("✔ {text}"));
+                } else {
+                    ui.weak(text);
+                }
+            }
+        });
+        ui.add(
+            egui::ProgressBar::new((self.current + 1) as f32 / self.steps.len().max(1) as f32)
+                .text(format!(
+                    "Step {}/{}: {}",
+                    self.current + 1,
+                    self.steps.len(),
+                    self.steps.get(self.current).copied().unwrap_or_default()
+                )),
+        );
+    }
+}
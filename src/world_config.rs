@@ -0,0 +1,477 @@
+use {
+    crate::paths,
+    anyhow::Context,
+    bevy::prelude::*,
+    bevy_egui::egui,
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        hash::{BuildHasher, RandomState},
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Plugin für "Configure World" im Singleplayer- und Host-Ablauf.
+///
+/// Das Formular lebt in [`WorldConfigForm`]; die daraus erzeugte [`WorldConfig`]
+/// wird mit dem neuen Spielstand gespeichert. chicken nimmt beim Start keine
+/// Welt-Parameter entgegen, der Server erzeugt seine Welt daher noch unabhängig
+/// davon.
+pub struct WorldConfigPlugin;
+
+impl Plugin for WorldConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WorldConfigForm::new(presets_dir()));
+    }
+}
+
+/// Verzeichnis der gespeicherten Welt-Presets
+fn presets_dir() -> PathBuf {
+    paths::config_dir().join("world_presets")
+}
+
+/// Weltgröße
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+}
+
+impl WorldSize {
+    pub const ALL: [WorldSize; 4] = [
+        WorldSize::Small,
+        WorldSize::Medium,
+        WorldSize::Large,
+        WorldSize::Huge,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldSize::Small => "Small",
+            WorldSize::Medium => "Medium",
+            WorldSize::Large => "Large",
+            WorldSize::Huge => "Huge",
+        }
+    }
+
+    /// Kantenlänge in Chunks
+    pub fn chunks(self) -> u32 {
+        match self {
+            WorldSize::Small => 64,
+            WorldSize::Medium => 128,
+            WorldSize::Large => 256,
+            WorldSize::Huge => 512,
+        }
+    }
+}
+
+/// Schwierigkeitsgrad
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Peaceful,
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Peaceful => "Peaceful",
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// Erweiterte Parameter der Weltgenerierung
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationParams {
+    /// Anteil der Welt unter dem Meeresspiegel (0-1)
+    pub sea_level: f32,
+    pub terrain_roughness: f32,
+    /// Multiplikator für die Größe von Biomen
+    pub biome_scale: f32,
+    pub resource_abundance: f32,
+    pub cave_density: f32,
+    pub generate_structures: bool,
+}
+
+impl GenerationParams {
+    pub const FACTOR: RangeInclusive<f32> = 0.25..=4.0;
+    pub const FRACTION: RangeInclusive<f32> = 0.0..=1.0;
+}
+
+impl Default for GenerationParams {
+    fn default() -> Self {
+        Self {
+            sea_level: 0.4,
+            terrain_roughness: 1.0,
+            biome_scale: 1.0,
+            resource_abundance: 1.0,
+            cave_density: 0.5,
+            generate_structures: true,
+        }
+    }
+}
+
+/// Vollständige Konfiguration einer neuen Welt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    /// Als Text gespeichert, da TOML nur vorzeichenbehaftete 64-Bit-Zahlen kennt
+    #[serde(with = "seed_as_string")]
+    pub seed: u64,
+    pub size: WorldSize,
+    pub difficulty: Difficulty,
+    pub generation: GenerationParams,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            seed: random_seed(),
+            size: WorldSize::default(),
+            difficulty: Difficulty::default(),
+            generation: GenerationParams::default(),
+        }
+    }
+}
+
+impl WorldConfig {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("serializing world config")?;
+        fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+mod seed_as_string {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(seed)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Zufälliger Seed ohne zusätzliche Abhängigkeit
+pub fn random_seed() -> u64 {
+    RandomState::new().hash_one(std::time::SystemTime::now())
+}
+
+/// Wandelt die Seed-Eingabe um: Zahlen direkt, sonst ein stabiler Hash des Texts
+pub fn parse_seed(input: &str) -> Option<u64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    Some(input.parse().unwrap_or_else(|_| fnv1a(input)))
+}
+
+/// FNV-1a, damit Text-Seeds über Versionen und Plattformen gleich bleiben
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Formularzustand von "Configure World"; gemeinsam für Singleplayer und Host
+#[derive(Resource, Debug)]
+pub struct WorldConfigForm {
+    pub config: WorldConfig,
+    pub seed_input: String,
+    pub preset_name: String,
+    presets_dir: PathBuf,
+    presets: Vec<String>,
+    status: Option<String>,
+}
+
+impl WorldConfigForm {
+    pub fn new(presets_dir: PathBuf) -> Self {
+        let config = WorldConfig::default();
+        let mut form = Self {
+            seed_input: config.seed.to_string(),
+            config,
+            preset_name: String::new(),
+            presets_dir,
+            presets: Vec::new(),
+            status: None,
+        };
+        form.refresh_presets();
+        form
+    }
+
+    /// Konfiguration mit dem Seed aus der Eingabe; `None` ohne gültigen Seed
+    pub fn to_config(&self) -> Option<WorldConfig> {
+        parse_seed(&self.seed_input).map(|seed| WorldConfig {
+            seed,
+            ..self.config.clone()
+        })
+    }
+
+    fn refresh_presets(&mut self) {
+        self.presets = fs::read_dir(&self.presets_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                    .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.presets.sort();
+    }
+
+    fn preset_path(&self, name: &str) -> PathBuf {
+        self.presets_dir.join(format!("{name}.toml"))
+    }
+
+    fn save_preset(&mut self) {
+        let name = self.preset_name.trim().to_string();
+        let Some(config) = self.to_config() else {
+            return;
+        };
+        self.status = Some(match config.save(&self.preset_path(&name)) {
+            Ok(()) => format!("Saved preset \"{name}\""),
+            Err(err) => format!("Could not save preset: {err:#}"),
+        });
+        self.refresh_presets();
+    }
+
+    fn load_preset(&mut self, name: &str) {
+        match WorldConfig::load(&self.preset_path(name)) {
+            Ok(config) => {
+                self.seed_input = config.seed.to_string();
+                self.config = config;
+                self.preset_name = name.to_string();
+                self.status = Some(format!("Loaded preset \"{name}\""));
+            }
+            Err(err) => self.status = Some(format!("Could not load preset: {err:#}")),
+        }
+    }
+
+    fn delete_preset(&mut self, name: &str) {
+        if let Err(err) = fs::remove_file(self.preset_path(name)) {
+            self.status = Some(format!("Could not delete preset: {err}"));
+        }
+        self.refresh_presets();
+    }
+}
+
+/// Preset-Namen werden zu Dateinamen, daher nur einfache Zeichen
+fn is_valid_preset_name(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-'))
+}
+
+// --- UI ---
+
+/// Rendert das Formular. Gibt `true` zurück, wenn die Konfiguration gültig ist.
+pub fn render_world_config_form(ui: &mut egui::Ui, form: &mut WorldConfigForm) -> bool {
+    let seed = parse_seed(&form.seed_input);
+
+    egui::Grid::new("world_config_form")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Seed");
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut form.seed_input)
+                        .hint_text("Number or text")
+                        .desired_width(180.0),
+                );
+                if ui.button("🎲").on_hover_text("Random seed").clicked() {
+                    form.seed_input = random_seed().to_string();
+                }
+                if let Some(seed) = seed
+                    && ui.button("📋").on_hover_text("Copy seed").clicked()
+                {
+                    ui.ctx().copy_text(seed.to_string());
+                }
+            });
+            ui.end_row();
+
+            ui.label("Size");
+            egui::ComboBox::from_id_salt("world_size")
+                .selected_text(form.config.size.label())
+                .show_ui(ui, |ui| {
+                    for size in WorldSize::ALL {
+                        ui.selectable_value(&mut form.config.size, size, size.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Difficulty");
+            egui::ComboBox::from_id_salt("world_difficulty")
+                .selected_text(form.config.difficulty.label())
+                .show_ui(ui, |ui| {
+                    for difficulty in Difficulty::ALL {
+                        ui.selectable_value(
+                            &mut form.config.difficulty,
+                            difficulty,
+                            difficulty.label(),
+                        );
+                    }
+                });
+            ui.end_row();
+        });
+
+    match seed {
+        Some(seed) if seed.to_string() != form.seed_input.trim() => {
+            ui.label(format!("Text seed resolves to {seed}"));
+        }
+        Some(_) => {}
+        None => {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 100, 100),
+                "Enter a seed or roll a random one",
+            );
+        }
+    }
+
+    egui::CollapsingHeader::new("Advanced generation").show(ui, |ui| {
+        render_generation_params(ui, &mut form.config.generation)
+    });
+
+    render_presets(ui, form);
+
+    seed.is_some()
+}
+
+fn render_generation_params(ui: &mut egui::Ui, params: &mut GenerationParams) {
+    ui.add(egui::Slider::new(&mut params.sea_level, GenerationParams::FRACTION).text("Sea level"));
+    ui.add(
+        egui::Slider::new(&mut params.terrain_roughness, GenerationParams::FACTOR)
+            .text("Terrain roughness"),
+    );
+    ui.add(
+        egui::Slider::new(&mut params.biome_scale, GenerationParams::FACTOR).text("Biome scale"),
+    );
+    ui.add(
+        egui::Slider::new(&mut params.resource_abundance, GenerationParams::FACTOR)
+            .text("Resource abundance"),
+    );
+    ui.add(
+        egui::Slider::new(&mut params.cave_density, GenerationParams::FRACTION)
+            .text("Cave density"),
+    );
+    ui.checkbox(&mut params.generate_structures, "Generate structures");
+    if ui.button("Reset advanced").clicked() {
+        *params = GenerationParams::default();
+    }
+}
+
+fn render_presets(ui: &mut egui::Ui, form: &mut WorldConfigForm) {
+    ui.separator();
+    ui.label("Presets");
+
+    let mut load = None;
+    let mut delete = None;
+    for name in &form.presets {
+        ui.horizontal(|ui| {
+            if ui.button(name).on_hover_text("Load preset").clicked() {
+                load = Some(name.clone());
+            }
+            if ui
+                .small_button("🗑")
+                .on_hover_text("Delete preset")
+                .clicked()
+            {
+                delete = Some(name.clone());
+            }
+        });
+    }
+    if let Some(name) = load {
+        form.load_preset(&name);
+    }
+    if let Some(name) = delete {
+        form.delete_preset(&name);
+    }
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut form.preset_name)
+                .hint_text("Preset name")
+                .desired_width(160.0),
+        );
+        let can_save = is_valid_preset_name(&form.preset_name) && form.to_config().is_some();
+        if ui
+            .add_enabled(can_save, egui::Button::new("Save preset"))
+            .clicked()
+        {
+            form.save_preset();
+        }
+    });
+
+    if let Some(status) = &form.status {
+        ui.small(status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_seeds_are_used_directly() {
+        assert_eq!(parse_seed(" 12345 "), Some(12345));
+        assert_eq!(parse_seed(""), None);
+    }
+
+    #[test]
+    fn text_seeds_hash_stably() {
+        assert_eq!(parse_seed("fos"), parse_seed("fos"));
+        assert_ne!(parse_seed("fos"), parse_seed("Fos"));
+        assert_eq!(parse_seed("a"), Some(0xaf63_dc4c_8601_ec8c));
+    }
+
+    #[test]
+    fn world_config_roundtrips_through_toml() {
+        let config = WorldConfig {
+            seed: 42,
+            size: WorldSize::Huge,
+            difficulty: Difficulty::Hard,
+            generation: GenerationParams {
+                generate_structures: false,
+                ..default()
+            },
+        };
+        let parsed: WorldConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed, config);
+    }
+}
@@ -1,6 +1,10 @@
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use clap::{Parser, Subcommand};
-use std::process::Command;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Parser)]
 #[command(name = "cargo xtask", about = "Dev task runner for campfire")]
@@ -16,6 +20,37 @@ enum Task {
         /// Version to release (e.g. 0.2.0 or v0.2.0)
         version: String,
     },
+    /// Install a .desktop entry that opens fos:// invite links with the client (Linux)
+    Desktop {
+        /// Client binary to register (default: target/release/client)
+        #[arg(long)]
+        exe: Option<PathBuf>,
+        /// Only write the entry into this release package directory, for a
+        /// `client` installed on the PATH
+        #[arg(long, value_name = "DIR", conflicts_with = "exe")]
+        package: Option<PathBuf>,
+    },
+    /// Load test: run headless clients against a server and report latency and errors
+    Swarm {
+        /// Number of bots
+        #[arg(long, default_value_t = 10)]
+        clients: u32,
+        /// Server address the bots join
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        target: String,
+        /// Seconds each bot stays on the server
+        #[arg(long, default_value_t = 60.0, value_parser = non_negative)]
+        duration: f32,
+        /// Average seconds between two chat messages of a bot
+        #[arg(long, default_value_t = 5.0, value_parser = positive)]
+        chat_interval: f32,
+        /// Chance (0..1) that a bot disconnects and rejoins after a message
+        #[arg(long, default_value_t = 0.1, value_parser = chance)]
+        reconnect_chance: f32,
+        /// Client binary to run (default: builds and uses target/debug/client)
+        #[arg(long)]
+        exe: Option<PathBuf>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -34,6 +69,27 @@ fn main() -> Result<()> {
 
     match cli.command {
         Task::Release { version } => release(version),
+        Task::Desktop { exe, package } => match package {
+            Some(dir) => package_desktop(&dir),
+            None => desktop(exe),
+        },
+        Task::Swarm {
+            clients,
+            target,
+            duration,
+            chat_interval,
+            reconnect_chance,
+            exe,
+        } => swarm(
+            clients,
+            &target,
+            Schedule {
+                duration,
+                chat_interval,
+                reconnect_chance,
+            },
+            exe,
+        ),
     }
 }
 
@@ -94,6 +150,386 @@ fn release(version: String) -> Result<()> {
     Ok(())
 }
 
+const DESKTOP_FILE: &str = "fos-client.desktop";
+
+/// .desktop entry that opens fos:// links with `exec`
+fn desktop_entry(exec: &str) -> String {
+    // %u: the desktop passes the clicked link as the first argument
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=FOS\n\
+         Comment=Join FOS servers from invite links\n\
+         Exec=\"{exec}\" %u\n\
+         Terminal=false\n\
+         Categories=Game;\n\
+         MimeType=x-scheme-handler/fos;\n"
+    )
+}
+
+/// Writes the entry into a release package; installing it registers the handler
+fn package_desktop(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(DESKTOP_FILE);
+    std::fs::write(&path, desktop_entry("client"))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn desktop(exe: Option<PathBuf>) -> Result<()> {
+    let exe = exe.unwrap_or_else(|| PathBuf::from("target/release/client"));
+    let exe = match exe.canonicalize() {
+        Ok(exe) => exe,
+        Err(_) => bail!(
+            "Client binary not found at {}. Build it first: cargo build --release",
+            exe.display()
+        ),
+    };
+
+    let data_home = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".local/share"),
+            None => bail!("Neither XDG_DATA_HOME nor HOME is set"),
+        },
+    };
+    let applications = data_home.join("applications");
+    std::fs::create_dir_all(&applications)?;
+
+    let path = applications.join(DESKTOP_FILE);
+    std::fs::write(&path, desktop_entry(&exe.display().to_string()))?;
+    println!("Wrote {}", path.display());
+
+    cmd(
+        "xdg-mime",
+        &["default", DESKTOP_FILE, "x-scheme-handler/fos"],
+    )
+    .context("registering the fos:// handler failed, is xdg-utils installed?")?;
+    // Only refreshes a cache; the entry works without it
+    if let Err(err) = cmd(
+        "update-desktop-database",
+        &[&applications.display().to_string()],
+    ) {
+        println!("⚠ {err}");
+    }
+
+    println!("✅ fos:// links now open {}", exe.display());
+    Ok(())
+}
+
+/// How long a bot's `wait-connected` waits for the server
+const CONNECT_WAIT_SECS: u32 = 30;
+/// Extra time for client startup and shutdown on top of a bot's script
+const BOT_SLACK: Duration = Duration::from_secs(120);
+
+/// What a single swarm bot does on the server
+struct Schedule {
+    duration: f32,
+    chat_interval: f32,
+    reconnect_chance: f32,
+}
+
+/// A running bot and where its output goes
+struct Bot {
+    name: String,
+    child: Child,
+    log: PathBuf,
+    /// A bot still running after this hangs
+    deadline: Instant,
+}
+
+/// What a bot printed in its headless report
+#[derive(Default)]
+struct BotReport {
+    connect_ms: Vec<u64>,
+    chat_ms: Vec<u64>,
+    sent: u64,
+    lost_chat: u64,
+    errors: Vec<String>,
+}
+
+fn swarm(clients: u32, target: &str, schedule: Schedule, exe: Option<PathBuf>) -> Result<()> {
+    if clients == 0 {
+        bail!("--clients must be at least 1");
+    }
+    // The bots get their own identity only through the XDG dirs, which other
+    // platforms ignore; all bots would then share the user's identity
+    if !cfg!(target_os = "linux") {
+        bail!("swarm only runs on Linux");
+    }
+
+    let exe = match exe {
+        Some(exe) => exe,
+        None => {
+            println!("Building client…");
+            cmd("cargo", &["build", "--bin", "client"])?;
+            PathBuf::from("target/debug/client")
+        }
+    };
+    let exe = exe
+        .canonicalize()
+        .with_context(|| format!("Client binary not found at {}", exe.display()))?;
+
+    // Absolute, the bots get it as their XDG dirs
+    let root = std::env::current_dir()?.join("target/swarm");
+    if root.exists() {
+        std::fs::remove_dir_all(&root)?;
+    }
+    let seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(1, |time| time.as_nanos() as u64);
+
+    println!(
+        "Starting {clients} bots against {target} for {:.0}s (logs in {})",
+        schedule.duration,
+        root.display()
+    );
+    let mut bots = Vec::new();
+    for index in 1..=clients {
+        let mut rng = Rng::new(seed ^ u64::from(index).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        match spawn_bot(&exe, &root, index, target, &schedule, &mut rng) {
+            Ok(bot) => bots.push(bot),
+            Err(err) => {
+                // Don't leave the bots that already started running on their own
+                for bot in &mut bots {
+                    let _ = bot.child.kill();
+                    let _ = bot.child.wait();
+                }
+                return Err(err);
+            }
+        }
+        // Staggered so the server does not see all handshakes in the same instant
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let mut reports = Vec::new();
+    let mut failed = Vec::new();
+    for mut bot in bots {
+        let status = loop {
+            if let Some(status) = bot.child.try_wait()? {
+                break Some(status);
+            }
+            // Bots exit on their own; one still running past its worst case hangs
+            if Instant::now() > bot.deadline {
+                bot.child.kill()?;
+                bot.child.wait()?;
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        };
+        let output = std::fs::read_to_string(&bot.log).unwrap_or_default();
+        let report = parse_report(&output);
+        let failure = match (status, &report) {
+            (None, _) => Some("killed after timeout".to_string()),
+            (Some(status), _) if !status.success() => Some(format!("exited with {status}")),
+            (_, None) => Some("no report".to_string()),
+            _ => None,
+        };
+        if let Some(failure) = failure {
+            failed.push(format!(
+                "{}: {failure}, see {}",
+                bot.name,
+                bot.log.display()
+            ));
+        }
+        reports.extend(report);
+    }
+
+    print_summary(clients, &reports, &failed);
+    if !failed.is_empty() {
+        bail!("{} of {clients} bots failed", failed.len());
+    }
+    Ok(())
+}
+
+fn spawn_bot(
+    exe: &Path,
+    root: &Path,
+    index: u32,
+    target: &str,
+    schedule: &Schedule,
+    rng: &mut Rng,
+) -> Result<Bot> {
+    let name = format!("Bot-{index}");
+    let dir = root.join(format!("bot-{index}"));
+    std::fs::create_dir_all(&dir)?;
+    let script = dir.join("script.txt");
+    let (content, budget) = bot_script(&name, target, schedule, rng);
+    std::fs::write(&script, content)?;
+    let log = dir.join("bot.log");
+    let out = File::create(&log)?;
+
+    // Own config and data dirs, so every bot creates and keeps its own identity
+    let child = Command::new(exe)
+        .args(["--headless", "--name", &name, "--script"])
+        .arg(&script)
+        .arg("--config")
+        .arg(dir.join("settings.toml"))
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("XDG_DATA_HOME", dir.join("data"))
+        .stdin(Stdio::null())
+        .stdout(out.try_clone()?)
+        .stderr(out)
+        .spawn()
+        .with_context(|| format!("starting {} failed", exe.display()))?;
+    Ok(Bot {
+        name,
+        child,
+        log,
+        deadline: Instant::now() + budget + BOT_SLACK,
+    })
+}
+
+/// Headless script: join, chat at random intervals, sometimes rejoin, leave.
+/// Also returns how long the script may take if every join waits the full time.
+fn bot_script(name: &str, target: &str, schedule: &Schedule, rng: &mut Rng) -> (String, Duration) {
+    let connect = format!("connect {target}\nwait-connected {CONNECT_WAIT_SECS}\n");
+    let mut script = format!("# {name}\n{connect}");
+    let mut budget = Duration::from_secs(CONNECT_WAIT_SECS.into());
+    let mut elapsed = 0.0;
+    let mut message = 0;
+    loop {
+        let wait = schedule.chat_interval * (0.5 + rng.next_f32());
+        if elapsed + wait > schedule.duration {
+            break;
+        }
+        elapsed += wait;
+        message += 1;
+        script.push_str(&format!("wait {wait:.2}\nsay {name} message {message}\n"));
+        if rng.next_f32() < schedule.reconnect_chance {
+            script.push_str(&format!("disconnect\nwait 1\n{connect}"));
+            budget += Duration::from_secs(1 + u64::from(CONNECT_WAIT_SECS));
+        }
+    }
+    script.push_str("disconnect\nexit\n");
+    (script, budget + Duration::from_secs_f32(elapsed))
+}
+
+fn parse_report(output: &str) -> Option<BotReport> {
+    let line = output
+        .lines()
+        .find_map(|line| line.strip_prefix("headless-report "))?;
+    let mut report = BotReport::default();
+    for field in line.split_whitespace() {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let millis = || value.split(',').filter_map(|ms| ms.parse().ok()).collect();
+        match key {
+            "connect_ms" => report.connect_ms = millis(),
+            "chat_ms" => report.chat_ms = millis(),
+            "sent" => report.sent = value.parse().unwrap_or(0),
+            "lost_chat" => report.lost_chat = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    report.errors = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("headless-error "))
+        .map(str::to_string)
+        .collect();
+    Some(report)
+}
+
+fn print_summary(clients: u32, reports: &[BotReport], failed: &[String]) {
+    let all = |field: fn(&BotReport) -> &Vec<u64>| {
+        let mut values: Vec<u64> = reports
+            .iter()
+            .flat_map(|report| field(report).clone())
+            .collect();
+        values.sort_unstable();
+        values
+    };
+    let connects = all(|report| &report.connect_ms);
+    let chats = all(|report| &report.chat_ms);
+    let sent: u64 = reports.iter().map(|report| report.sent).sum();
+    let lost: u64 = reports.iter().map(|report| report.lost_chat).sum();
+
+    let mut errors = BTreeMap::<&str, usize>::new();
+    for error in reports.iter().flat_map(|report| &report.errors) {
+        *errors.entry(error).or_default() += 1;
+    }
+
+    println!();
+    println!(
+        "Bots:       {} ok, {} failed of {clients}",
+        clients as usize - failed.len(),
+        failed.len()
+    );
+    println!("Connects:   {}", connects.len());
+    println!("Connect ms: {}", latency(&connects));
+    println!("Chat ms:    {}", latency(&chats));
+    println!("Messages:   {sent} sent, {lost} without echo");
+    if !errors.is_empty() {
+        println!("Errors:");
+        for (error, count) in errors {
+            println!("  {count:>4} × {error}");
+        }
+    }
+    for failure in failed {
+        println!("⚠ {failure}");
+    }
+}
+
+/// min/avg/p95/max of sorted values
+fn latency(sorted: &[u64]) -> String {
+    let (Some(min), Some(max)) = (sorted.first(), sorted.last()) else {
+        return "-".to_string();
+    };
+    let avg = sorted.iter().sum::<u64>() / sorted.len() as u64;
+    let p95 = sorted[(sorted.len() * 95).div_ceil(100).saturating_sub(1)];
+    format!("min {min} / avg {avg} / p95 {p95} / max {max}")
+}
+
+/// Finite number for the swarm options; NaN or infinity would never end a bot script
+fn number(argument: &str) -> Result<f32, String> {
+    argument
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("'{argument}' is not a number"))
+}
+
+fn non_negative(argument: &str) -> Result<f32, String> {
+    let value = number(argument)?;
+    if value < 0.0 {
+        return Err("must not be negative".to_string());
+    }
+    Ok(value)
+}
+
+fn positive(argument: &str) -> Result<f32, String> {
+    let value = number(argument)?;
+    if value <= 0.0 {
+        return Err("must be positive".to_string());
+    }
+    Ok(value)
+}
+
+fn chance(argument: &str) -> Result<f32, String> {
+    let value = number(argument)?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err("must be between 0 and 1".to_string());
+    }
+    Ok(value)
+}
+
+/// xorshift64; enough to vary the bot schedules without a rand dependency
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
 fn cmd(program: &str, args: &[&str]) -> Result<String> {
     let output = Command::new(program).args(args).output()?;
     if !output.status.success() {
@@ -106,3 +542,83 @@ fn cmd(program: &str, args: &[&str]) -> Result<String> {
     }
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_report_reads_headless_report() {
+        let output = "log line\nheadless-report connect_ms=250,1200 chat_ms=18 sent=2 lost_chat=1 errors=1\nheadless-error connection lost\n";
+        let report = parse_report(output).unwrap();
+        assert_eq!(report.connect_ms, [250, 1200]);
+        assert_eq!(report.chat_ms, [18]);
+        assert_eq!(report.sent, 2);
+        assert_eq!(report.lost_chat, 1);
+        assert_eq!(report.errors, ["connection lost"]);
+
+        let empty =
+            parse_report("headless-report connect_ms= chat_ms= sent=0 lost_chat=0 errors=0")
+                .unwrap();
+        assert!(empty.connect_ms.is_empty() && empty.chat_ms.is_empty());
+        assert!(parse_report("crashed before the report").is_none());
+    }
+
+    #[test]
+    fn bot_script_fits_the_schedule() {
+        let schedule = Schedule {
+            duration: 20.0,
+            chat_interval: 2.0,
+            reconnect_chance: 0.0,
+        };
+        let (script, budget) = bot_script("Bot-1", "127.0.0.1:8080", &schedule, &mut Rng::new(7));
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["# Bot-1", "connect 127.0.0.1:8080", "wait-connected 30"]
+        );
+        assert_eq!(lines[lines.len() - 2..], ["disconnect", "exit"]);
+        assert!(!script.contains("disconnect\nwait 1"));
+
+        let waited: f32 = lines
+            .iter()
+            .filter_map(|line| line.strip_prefix("wait "))
+            .map(|seconds| seconds.parse::<f32>().unwrap())
+            .sum();
+        assert!(waited <= schedule.duration + 0.01);
+        assert!(budget >= Duration::from_secs(30));
+        assert!(budget <= Duration::from_secs_f32(30.0 + schedule.duration));
+
+        // Always rejoining adds a full connect per message
+        let schedule = Schedule {
+            reconnect_chance: 1.0,
+            ..schedule
+        };
+        let (script, _) = bot_script("Bot-2", "127.0.0.1:8080", &schedule, &mut Rng::new(7));
+        let messages = script.matches("say Bot-2").count();
+        assert!(messages > 0);
+        assert_eq!(
+            script.matches("connect 127.0.0.1:8080").count(),
+            messages + 1
+        );
+    }
+
+    #[test]
+    fn latency_summarizes_sorted_values() {
+        assert_eq!(latency(&[]), "-");
+        assert_eq!(latency(&[5]), "min 5 / avg 5 / p95 5 / max 5");
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(latency(&values), "min 1 / avg 50 / p95 95 / max 100");
+    }
+
+    #[test]
+    fn swarm_options_reject_invalid_numbers() {
+        assert_eq!(non_negative("0"), Ok(0.0));
+        assert!(non_negative("-1").is_err());
+        assert!(non_negative("NaN").is_err());
+        assert!(positive("0").is_err());
+        assert!(positive("inf").is_err());
+        assert_eq!(chance("0.5"), Ok(0.5));
+        assert!(chance("1.5").is_err());
+    }
+}